@@ -0,0 +1,107 @@
+use std::borrow::Cow;
+use std::convert::Infallible;
+use std::fmt;
+use std::str::FromStr;
+
+/// A puzzle answer: a signed or unsigned integer of up to 128 bits, or text.
+///
+/// Integers compare by value regardless of their width, so `I64(5) == U128(5)`.
+/// An integer and a string are equal when the string is the integer's decimal form.
+#[derive(Debug, Clone, Eq)]
+pub enum Answer {
+    I64(i64),
+    U64(u64),
+    I128(i128),
+    U128(u128),
+    Str(Cow<'static, str>),
+}
+
+impl Answer {
+    /// Sign and magnitude of an integer answer, `None` for text
+    fn integer(&self) -> Option<(bool, u128)> {
+        match *self {
+            Answer::I64(n) => Some((n < 0, n.unsigned_abs() as u128)),
+            Answer::U64(n) => Some((false, n as u128)),
+            Answer::I128(n) => Some((n < 0, n.unsigned_abs())),
+            Answer::U128(n) => Some((false, n)),
+            Answer::Str(_) => None,
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Answer) -> bool {
+        match (self.integer(), other.integer()) {
+            // -0 cannot occur, so sign and magnitude identify the value
+            (Some(a), Some(b)) => a == b,
+            _ => self.to_string() == other.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::I64(n) => n.fmt(f),
+            Answer::U64(n) => n.fmt(f),
+            Answer::I128(n) => n.fmt(f),
+            Answer::U128(n) => n.fmt(f),
+            Answer::Str(s) => s.fmt(f),
+        }
+    }
+}
+
+/// Reads an answer as the narrowest integer that holds it, or as text
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    fn from_str(text: &str) -> Result<Answer, Infallible> {
+        let text = text.trim();
+        Ok(if let Ok(n) = text.parse() {
+            Answer::I64(n)
+        } else if let Ok(n) = text.parse() {
+            Answer::U64(n)
+        } else if let Ok(n) = text.parse() {
+            Answer::I128(n)
+        } else if let Ok(n) = text.parse() {
+            Answer::U128(n)
+        } else {
+            Answer::Str(Cow::Owned(text.to_string()))
+        })
+    }
+}
+
+macro_rules! answer_from {
+    ($($source:ty => $variant:ident as $target:ty),* $(,)?) => {
+        $(
+            impl From<$source> for Answer {
+                fn from(n: $source) -> Answer {
+                    Answer::$variant(n as $target)
+                }
+            }
+        )*
+    };
+}
+
+answer_from! {
+    i32 => I64 as i64,
+    i64 => I64 as i64,
+    isize => I64 as i64,
+    u32 => U64 as u64,
+    u64 => U64 as u64,
+    usize => U64 as u64,
+    i128 => I128 as i128,
+    u128 => U128 as u128,
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Answer {
+        Answer::Str(Cow::Owned(s))
+    }
+}
+
+impl From<&'static str> for Answer {
+    fn from(s: &'static str) -> Answer {
+        Answer::Str(Cow::Borrowed(s))
+    }
+}
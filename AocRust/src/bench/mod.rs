@@ -0,0 +1,124 @@
+use std::fmt;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use crate::utils::format_duration;
+
+/// How long and how often to repeat a solver
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchConfig {
+    /// Untimed runs before measuring, to warm caches and the allocator
+    pub warmup: usize,
+    /// Exact number of timed runs; when `None` the time budget decides
+    pub iterations: Option<usize>,
+    /// Total time to spend on timed runs when `iterations` is `None`
+    pub time_budget: Duration,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        BenchConfig {
+            warmup: 3,
+            iterations: None,
+            time_budget: Duration::from_secs(1),
+        }
+    }
+}
+
+/// Lower and upper bound on the sample count when running on a time budget
+const MIN_SAMPLES: usize = 5;
+const MAX_SAMPLES: usize = 100_000;
+
+/// Summary statistics over the timed runs
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// Computes the statistics of a non-empty set of samples
+    pub fn from_samples(samples: &[Duration]) -> Stats {
+        assert!(!samples.is_empty(), "no samples to summarize");
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+
+        let runs = sorted.len();
+        let seconds: Vec<f64> = sorted.iter().map(Duration::as_secs_f64).collect();
+        let mean = seconds.iter().sum::<f64>() / runs as f64;
+        let variance = seconds.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / runs as f64;
+
+        let median = if runs % 2 == 1 {
+            sorted[runs / 2]
+        } else {
+            (sorted[runs / 2 - 1] + sorted[runs / 2]) / 2
+        };
+
+        Stats {
+            runs,
+            min: sorted[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            p95: sorted[percentile_index(runs, 0.95)],
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+/// Nearest-rank index of a percentile in a sorted list of `len` samples
+fn percentile_index(len: usize, percentile: f64) -> usize {
+    let rank = (percentile * len as f64).ceil() as usize;
+    rank.clamp(1, len) - 1
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:>7} {:>10} {:>10} {:>10} {:>10} {:>10}",
+            self.runs,
+            format_duration(self.min),
+            format_duration(self.median),
+            format_duration(self.mean),
+            format_duration(self.p95),
+            format_duration(self.stddev),
+        )
+    }
+}
+
+/// Column headers matching the [`Stats`] display format
+pub const STATS_HEADER: &str = "   Runs        Min     Median       Mean        p95     StdDev";
+
+/// Times `f` repeatedly according to `config`
+pub fn bench<T>(config: &BenchConfig, mut f: impl FnMut() -> T) -> Stats {
+    for _ in 0..config.warmup {
+        black_box(f());
+    }
+
+    let mut samples = Vec::new();
+    let started = Instant::now();
+
+    loop {
+        let start = Instant::now();
+        black_box(f());
+        samples.push(start.elapsed());
+
+        let done = match config.iterations {
+            Some(iterations) => samples.len() >= iterations,
+            None => {
+                samples.len() >= MAX_SAMPLES
+                    || (samples.len() >= MIN_SAMPLES && started.elapsed() >= config.time_budget)
+            }
+        };
+        if done {
+            break;
+        }
+    }
+
+    Stats::from_samples(&samples)
+}
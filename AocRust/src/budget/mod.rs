@@ -0,0 +1,107 @@
+use std::sync::RwLock;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::error::SolveError;
+use crate::parallel;
+use crate::utils::format_duration;
+
+/// How long and how many steps a single part may take; `None` means no limit
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Limits {
+    pub time: Option<Duration>,
+    pub steps: Option<u64>,
+}
+
+/// The `--timeout` and `--max-steps` settings every run starts its budgets from;
+/// without `--max-steps` a day's own step limit applies
+static LIMITS: RwLock<Limits> = RwLock::new(Limits { time: None, steps: None });
+
+pub fn set_limits(limits: Limits) {
+    *LIMITS.write().unwrap_or_else(|poisoned| poisoned.into_inner()) = limits;
+}
+
+pub fn limits() -> Limits {
+    *LIMITS.read().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Steps between two looks at the clock, which is slower than counting
+const CLOCK_INTERVAL: u64 = 1024;
+
+/// A time and step allowance handed to a solver. Long searches call
+/// [`Budget::step`] as they go and stop with [`SolveError::TimedOut`] once it
+/// runs out or [`Budget::cancel`] is called, so a part never hangs or guesses.
+/// The budget is shared by reference, so worker threads draw from the same one.
+#[derive(Debug)]
+pub struct Budget {
+    limits: Limits,
+    start: Instant,
+    steps: AtomicU64,
+    /// The first [`parallel::try_map`] item the budget is cancelled for, and every
+    /// one after it; `usize::MAX` while nothing is cancelled
+    cancelled_from: AtomicUsize,
+}
+
+impl Budget {
+    /// Starts the clock on a new budget
+    pub fn new(limits: Limits) -> Self {
+        Budget {
+            limits,
+            start: Instant::now(),
+            steps: AtomicU64::new(0),
+            cancelled_from: AtomicUsize::new(usize::MAX),
+        }
+    }
+
+    /// A budget that only runs out when cancelled
+    pub fn unlimited() -> Self {
+        Budget::new(Limits::default())
+    }
+
+    /// A budget with the limits given on the command line, or `default_steps`
+    /// when no step limit was given
+    pub fn from_settings(default_steps: Option<u64>) -> Self {
+        let limits = limits();
+        Budget::new(Limits { steps: limits.steps.or(default_steps), ..limits })
+    }
+
+    /// Makes every later [`Budget::step`] or [`Budget::check`] fail
+    pub fn cancel(&self) {
+        self.cancel_from(0);
+    }
+
+    /// Like [`Budget::cancel`], but only for the [`parallel::try_map`] items from
+    /// `item` on; work outside `try_map` counts as item 0
+    pub fn cancel_from(&self, item: usize) {
+        self.cancelled_from.fetch_min(item, Ordering::Relaxed);
+    }
+
+    /// Steps taken so far
+    pub fn steps(&self) -> u64 {
+        self.steps.load(Ordering::Relaxed)
+    }
+
+    /// Counts one unit of work, failing once the budget is spent
+    pub fn step(&self) -> Result<(), SolveError> {
+        let steps = self.steps.fetch_add(1, Ordering::Relaxed) + 1;
+        if let Some(max) = self.limits.steps
+            && steps > max
+        {
+            return Err(SolveError::TimedOut(format!("no answer within {} steps", max)));
+        }
+        if steps.is_multiple_of(CLOCK_INTERVAL) { self.check() } else { Ok(()) }
+    }
+
+    /// Fails if the budget was cancelled or its time is up, without counting a step
+    pub fn check(&self) -> Result<(), SolveError> {
+        if parallel::current_item() >= self.cancelled_from.load(Ordering::Relaxed) {
+            return Err(SolveError::TimedOut("cancelled".to_string()));
+        }
+        match self.limits.time {
+            Some(time) if self.start.elapsed() > time => {
+                Err(SolveError::TimedOut(format!("no answer within {}", format_duration(time))))
+            }
+            _ => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,391 @@
+use std::fmt;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use aoc_rust::bench::BenchConfig;
+use aoc_rust::budget::Limits;
+use aoc_rust::report::Format;
+use aoc_rust::solution::Part;
+use aoc_rust::watch;
+
+pub const USAGE: &str = "\
+Usage: aoc_rust [COMMAND] [OPTIONS] [INPUT]
+
+Commands:
+  run    Solve a day and print the answers
+  test   Run a day's example checks followed by its real input
+  verify Compare real-input answers with those recorded in docs/problems
+  examples
+         Compare the examples in docs/problems with <inputs>/dayNN.cases.toml
+         and its input files; --write updates the manifest from the docs
+  bench  Time each part repeatedly and report min/median/mean/p95/stddev
+  watch  Rerun a day whenever its source or input files change
+  new-day N
+         Generate the module, registry entry, inputs and docs stub for day N
+  help   Print this message
+
+Without a command, the interactive day selector is started.
+
+Options:
+  -d, --day N         Day to run (1-25)
+  -p, --part P        Only run part P (1 or 2)
+  -i, --input PATH    Read the puzzle input from PATH instead of <inputs>/dayNN.txt;
+                      `-` reads standard input. May also be given as INPUT
+  --part1-input PATH  Input for part 1 only (overrides --input)
+  --part2-input PATH  Input for part 2 only (overrides --input)
+  -a, --all           Run every implemented day
+  --format FMT        Print run, test or verify results as json, csv or junit
+                      instead of text
+  --inputs DIR        Directory holding dayNN.txt and dayNN.cases.toml. Defaults to
+                      $AOC_INPUTS, then `inputs = \"DIR\"` in the nearest aoc_rust.toml,
+                      then the nearest inputs/ directory above the current one
+  -j, --jobs N        Threads to use: days run concurrently and days with
+                      independent units (machines, regions, ranges, problems)
+                      split them across threads (default 1)
+  --timeout TIME      Give up on a part after TIME, e.g. 500ms or 10s, and report
+                      it as timed out (default: no limit)
+  --max-steps N       Give up on a part after N search steps (default: no limit,
+                      except the days with long searches set their own)
+
+Run and bench options:
+  --param NAME=VALUE  Change one of the day's parameters, e.g. --param connections=10
+                      for day 8; may be repeated. Needs a single --day
+
+Watch options:
+  --interval TIME     How often to check the files for changes (default 500ms)
+
+Examples options:
+  --write             Write each day's manifest, dayNN.cases.toml, next to its inputs
+
+New-day options:
+  --title TEXT        Puzzle title (default: from docs/problems/dayNN.md, if present)
+
+Bench options:
+  --warmup N          Untimed runs before measuring (default 3)
+  --iterations N      Timed runs per part instead of a time budget
+  --time-budget TIME  Time to spend per part, e.g. 500ms or 2s (default 1s)
+
+Examples:
+  aoc_rust run --day 7 --part 2 --input ../inputs/day07.txt
+  cat edited.txt | aoc_rust run -d 5 -
+  aoc_rust run -d 11 --part1-input small.txt --part2-input other.txt
+  aoc_rust run --all --inputs ~/aoc/2025
+  aoc_rust test --day 11
+  aoc_rust verify --all
+  aoc_rust examples --all --write
+  aoc_rust test --all --format junit > results.xml
+  aoc_rust run --all --jobs 8
+  aoc_rust test --all --timeout 10s
+  aoc_rust bench --day 8 --time-budget 2s
+  aoc_rust run -d 1 --param size=10 --param start=0 dial.txt
+  aoc_rust watch --day 8
+  aoc_rust new-day 13 --title \"Some Title\"";
+
+/// Which days a command applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Days {
+    One(u32),
+    All,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Interactive,
+    Help,
+    Run {
+        days: Days,
+        part: Option<Part>,
+        /// Input for both parts; `-` is standard input
+        input: Option<String>,
+        /// Per-part inputs that take precedence over `input`
+        part_inputs: [Option<String>; 2],
+        /// Machine-readable output instead of text
+        format: Option<Format>,
+        /// `--param` overrides of the day's parameters, in order
+        params: Vec<(String, String)>,
+    },
+    Test {
+        days: Days,
+        format: Option<Format>,
+    },
+    Verify {
+        days: Days,
+        format: Option<Format>,
+    },
+    Examples {
+        days: Days,
+        /// Save the extracted examples as each day's manifest
+        write: bool,
+    },
+    Bench {
+        days: Days,
+        part: Option<Part>,
+        config: BenchConfig,
+        params: Vec<(String, String)>,
+    },
+    Watch {
+        day: u32,
+        /// Time between two checks of the watched files
+        interval: Duration,
+    },
+    NewDay {
+        day: u32,
+        title: Option<String>,
+    },
+}
+
+/// A parsed command line: the command plus the options every command accepts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Args {
+    pub command: Command,
+    pub inputs: Option<PathBuf>,
+    /// Worker threads for days and for independent units within a day
+    pub jobs: usize,
+    /// Time and step budget for every part that is solved
+    pub limits: Limits,
+}
+
+/// Error produced when the command line cannot be understood
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UsageError(pub String);
+
+impl fmt::Display for UsageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Parses the arguments that follow the program name
+pub fn parse_args<I, S>(args: I) -> Result<Args, UsageError>
+where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+{
+    let mut args = args.into_iter().map(Into::into).peekable();
+
+    // Options without a command apply to the interactive day selector
+    let command = match args.peek() {
+        Some(arg) if !arg.starts_with('-') => args.next().unwrap_or_default(),
+        _ => String::new(),
+    };
+
+    let mut day = None;
+    let mut part = None;
+    let mut input = None;
+    let mut part_inputs = [None, None];
+    let mut inputs = None;
+    let mut jobs = 1;
+    let mut limits = Limits::default();
+    let mut format = None;
+    let mut all = false;
+    let mut bench = BenchConfig::default();
+    let mut interval = watch::DEFAULT_INTERVAL;
+    let mut title = None;
+    let mut write = false;
+    let mut params = Vec::new();
+    // Flags given on the command line, so each command can reject the ones it ignores
+    let mut seen: Vec<String> = Vec::new();
+
+    while let Some(arg) = args.next() {
+        // A lone `-` (stdin) or a bare path is the positional INPUT
+        if arg == "-" || !arg.starts_with('-') {
+            if input.replace(arg).is_some() {
+                return Err(UsageError("the input can only be given once".to_string()));
+            }
+            seen.push("--input".to_string());
+            continue;
+        }
+
+        let arg = match arg.as_str() {
+            "-d" => "--day".to_string(),
+            "-p" => "--part".to_string(),
+            "-i" => "--input".to_string(),
+            "-a" => "--all".to_string(),
+            "-j" => "--jobs".to_string(),
+            _ => arg,
+        };
+        match arg.as_str() {
+            "--day" => day = Some(parse_day(&value_for(&arg, args.next())?)?),
+            "--part" => part = Some(parse_part(&value_for(&arg, args.next())?)?),
+            "--input" => {
+                if input.replace(value_for(&arg, args.next())?).is_some() {
+                    return Err(UsageError("the input can only be given once".to_string()));
+                }
+            }
+            "--part1-input" => part_inputs[0] = Some(value_for(&arg, args.next())?),
+            "--part2-input" => part_inputs[1] = Some(value_for(&arg, args.next())?),
+            "--inputs" => inputs = Some(PathBuf::from(value_for(&arg, args.next())?)),
+            "--jobs" => jobs = parse_count(&arg, &value_for(&arg, args.next())?, 1)?,
+            "--timeout" => limits.time = Some(parse_duration(&value_for(&arg, args.next())?)?),
+            "--max-steps" => {
+                limits.steps = Some(parse_count(&arg, &value_for(&arg, args.next())?, 1)? as u64)
+            }
+            "--all" => all = true,
+            "--format" => format = Some(parse_format(&value_for(&arg, args.next())?)?),
+            "--warmup" => bench.warmup = parse_count(&arg, &value_for(&arg, args.next())?, 0)?,
+            "--iterations" => {
+                bench.iterations = Some(parse_count(&arg, &value_for(&arg, args.next())?, 1)?)
+            }
+            "--time-budget" => bench.time_budget = parse_duration(&value_for(&arg, args.next())?)?,
+            "--interval" => interval = parse_duration(&value_for(&arg, args.next())?)?,
+            "--title" => title = Some(value_for(&arg, args.next())?),
+            "--write" => write = true,
+            "--param" => params.push(parse_param(&value_for(&arg, args.next())?)?),
+            "-h" | "--help" => return Ok(Args { command: Command::Help, inputs, jobs, limits }),
+            other => return Err(UsageError(format!("Unknown option '{}'", other))),
+        }
+        seen.push(arg);
+    }
+
+    let days = match (day, all) {
+        (Some(_), true) => return Err(UsageError("--day and --all cannot be combined".to_string())),
+        (Some(day), false) => Some(Days::One(day)),
+        (None, true) => Some(Days::All),
+        (None, false) => None,
+    };
+    let needs_days = || UsageError(format!("{} needs --day N or --all", command));
+
+    let command = match command.as_str() {
+        "" => {
+            allow_only("the day selector", &seen, &[])?;
+            Command::Interactive
+        }
+        "run" => {
+            allow_only(
+                &command,
+                &seen,
+                &["--day", "--all", "--part", "--input", "--part1-input", "--part2-input", "--format", "--param"],
+            )?;
+            let days = days.ok_or_else(needs_days)?;
+            let explicit = input.is_some() || part_inputs.iter().any(Option::is_some);
+            if explicit && days == Days::All {
+                return Err(UsageError("an explicit input needs a single --day".to_string()));
+            }
+            if !params.is_empty() && days == Days::All {
+                return Err(UsageError("--param needs a single --day".to_string()));
+            }
+            Command::Run { days, part, input, part_inputs, format, params }
+        }
+        "test" => {
+            allow_only(&command, &seen, &["--day", "--all", "--format"])?;
+            Command::Test { days: days.ok_or_else(needs_days)?, format }
+        }
+        "verify" => {
+            allow_only(&command, &seen, &["--day", "--all", "--format"])?;
+            Command::Verify { days: days.ok_or_else(needs_days)?, format }
+        }
+        "examples" => {
+            allow_only(&command, &seen, &["--day", "--all", "--write"])?;
+            Command::Examples { days: days.ok_or_else(needs_days)?, write }
+        }
+        "bench" => {
+            allow_only(
+                &command,
+                &seen,
+                &["--day", "--all", "--part", "--warmup", "--iterations", "--time-budget", "--param"],
+            )?;
+            let days = days.ok_or_else(needs_days)?;
+            if !params.is_empty() && days == Days::All {
+                return Err(UsageError("--param needs a single --day".to_string()));
+            }
+            Command::Bench { days, part, config: bench, params }
+        }
+        "watch" => {
+            allow_only(&command, &seen, &["--day", "--interval"])?;
+            match days {
+                Some(Days::One(day)) => Command::Watch { day, interval },
+                Some(Days::All) => return Err(UsageError("watch needs a single --day".to_string())),
+                None => return Err(needs_days()),
+            }
+        }
+        "new-day" => {
+            // The day may be given as `new-day N` as well as with --day
+            allow_only(&command, &seen, &["--day", "--input", "--title"])?;
+            let day = match (day, input) {
+                (Some(day), None) => day,
+                (None, Some(day)) => parse_day(&day)?,
+                _ => return Err(UsageError("new-day needs a single day, e.g. new-day 13".to_string())),
+            };
+            Command::NewDay { day, title }
+        }
+        "help" => Command::Help,
+        other => return Err(UsageError(format!("Unknown command '{}'", other))),
+    };
+
+    Ok(Args { command, inputs, jobs, limits })
+}
+
+/// Flags every command accepts
+const GLOBAL_FLAGS: &[&str] = &["--inputs", "--jobs", "--timeout", "--max-steps"];
+
+/// Rejects flags the command does not use; [`GLOBAL_FLAGS`] are accepted everywhere
+fn allow_only(command: &str, seen: &[String], allowed: &[&str]) -> Result<(), UsageError> {
+    match seen
+        .iter()
+        .find(|flag| !GLOBAL_FLAGS.contains(&flag.as_str()) && !allowed.contains(&flag.as_str()))
+    {
+        Some(flag) => Err(UsageError(format!("{} does not accept {}", command, flag))),
+        None => Ok(()),
+    }
+}
+
+fn value_for(flag: &str, value: Option<String>) -> Result<String, UsageError> {
+    value.ok_or_else(|| UsageError(format!("{} needs a value", flag)))
+}
+
+fn parse_day(value: &str) -> Result<u32, UsageError> {
+    match value.parse::<u32>() {
+        Ok(day) if (1..=25).contains(&day) => Ok(day),
+        _ => Err(UsageError(format!("Invalid day '{}', expected 1-25", value))),
+    }
+}
+
+fn parse_part(value: &str) -> Result<Part, UsageError> {
+    value
+        .parse::<u8>()
+        .ok()
+        .and_then(Part::from_number)
+        .ok_or_else(|| UsageError(format!("Invalid part '{}', expected 1 or 2", value)))
+}
+
+/// Splits `NAME=VALUE`; the value is checked by the day once it is known
+fn parse_param(value: &str) -> Result<(String, String), UsageError> {
+    match value.split_once('=') {
+        Some((name, value)) if !name.trim().is_empty() => Ok((name.trim().to_string(), value.to_string())),
+        _ => Err(UsageError(format!("Invalid parameter '{}', expected NAME=VALUE", value))),
+    }
+}
+
+fn parse_format(value: &str) -> Result<Format, UsageError> {
+    Format::from_name(value)
+        .ok_or_else(|| UsageError(format!("Invalid format '{}', expected json, csv or junit", value)))
+}
+
+fn parse_count(flag: &str, value: &str, min: usize) -> Result<usize, UsageError> {
+    match value.parse::<usize>() {
+        Ok(count) if count >= min => Ok(count),
+        _ => Err(UsageError(format!("{} needs a number of at least {}, got '{}'", flag, min, value))),
+    }
+}
+
+/// Parses durations such as `500ms`, `2s` or `1.5s`; a bare number means seconds
+fn parse_duration(value: &str) -> Result<Duration, UsageError> {
+    let split = value
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+
+    let scale = match unit {
+        "" | "s" => 1.0,
+        "ms" => 1e-3,
+        "us" | "µs" => 1e-6,
+        "ns" => 1e-9,
+        _ => return Err(UsageError(format!("Invalid duration '{}', expected e.g. 500ms or 2s", value))),
+    };
+
+    match number.parse::<f64>() {
+        Ok(number) if number > 0.0 => Duration::try_from_secs_f64(number * scale)
+            .map_err(|_| UsageError(format!("Duration '{}' is too long", value))),
+        _ => Err(UsageError(format!("Invalid duration '{}', expected e.g. 500ms or 2s", value))),
+    }
+}
@@ -1,27 +1,95 @@
-/// Advent of Code 2025 - Day 01: Secret Entrance
+//! Advent of Code 2025 - Day 01: Secret Entrance
+
+use crate::answer::Answer;
+use crate::budget::Budget;
+use crate::error::{self, ParseError, SolveError};
+use crate::params::{self, Params, ParamsError};
+use crate::solution::Solution;
+use crate::utils::Input;
+
+/// A single dial rotation: `L` or `R` followed by the number of clicks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rotation {
+    pub direction: u8,
+    pub distance: i32,
+}
 
-use crate::utils;
+/// The dial the rotations turn: it shows `0..size` and starts at `start`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Dial {
+    pub size: i32,
+    pub start: i32,
+}
 
-/// Count how many times the dial points at 0 after rotations
-pub fn part1(input: &str) -> i32 {
-    let rotations = input.split_whitespace();
+impl Default for Dial {
+    fn default() -> Self {
+        Dial { size: 100, start: 50 }
+    }
+}
 
-    let mut position: i32 = 50; // Starting position
-    let mut zero_count = 0;
+impl Params for Dial {
+    const NAMES: &'static [&'static str] = &["size", "start"];
 
-    for rotation in rotations {
-        let bytes = rotation.as_bytes();
-        if bytes.len() < 2 { continue; }
+    fn set(&mut self, name: &str, value: &str) -> Result<(), ParamsError> {
+        match name {
+            "size" => self.size = params::value(name, value)?,
+            "start" => self.start = params::value(name, value)?,
+            _ => return Err(params::unknown(name, Self::NAMES)),
+        }
+        Ok(())
+    }
 
-        let direction = bytes[0];
-        let distance: i32 = rotation[1..].parse().unwrap();
+    fn validate(&self) -> Result<(), ParamsError> {
+        if self.size < 1 {
+            return Err(ParamsError(format!("the dial size must be at least 1, got {}", self.size)));
+        }
+        if !(0..self.size).contains(&self.start) {
+            return Err(ParamsError(format!("start {} is not on a dial of size {}", self.start, self.size)));
+        }
+        Ok(())
+    }
+}
+
+/// Parses the whitespace separated rotations
+pub fn parse(input: &str) -> Result<Vec<Rotation>, ParseError> {
+    let input = Input::new(input);
+    let mut rotations = Vec::new();
+
+    for (line_number, line) in input.lines() {
+        for rotation in line.split_whitespace() {
+            let direction = rotation.as_bytes()[0];
+            if direction != b'L' && direction != b'R' {
+                return Err(ParseError::at(
+                    1,
+                    line_number,
+                    line,
+                    rotation,
+                    format!("expected a rotation like L68 or R48, found '{}'", rotation),
+                ));
+            }
+
+            rotations.push(Rotation {
+                direction,
+                distance: error::parse_number(1, line_number, line, &rotation[1..])?,
+            });
+        }
+    }
 
+    Ok(rotations)
+}
+
+/// Count how many times the dial points at 0 after rotations
+pub fn part1(rotations: &[Rotation], dial: &Dial) -> i32 {
+    let mut position = dial.start;
+    let mut zero_count = 0;
+
+    for &Rotation { direction, distance } in rotations {
         match direction {
             b'L' => {
-                position = (position - distance).rem_euclid(100);
+                position = (position - distance).rem_euclid(dial.size);
             }
             b'R' => {
-                position = (position + distance).rem_euclid(100);
+                position = (position + distance).rem_euclid(dial.size);
             }
             _ => {}
         }
@@ -35,34 +103,27 @@ pub fn part1(input: &str) -> i32 {
 }
 
 /// Part 2: Count number of times dial points at 0 during rotations
-pub fn part2(input: &str) -> i32 {
-    let rotations = input.split_whitespace();
-
-    let mut position: i32 = 50; // Starting position
+pub fn part2(rotations: &[Rotation], dial: &Dial) -> i32 {
+    let mut position = dial.start;
+    let size = dial.size;
     let mut zero_count = 0;
 
-    for rotation in rotations {
-        let bytes = rotation.as_bytes();
-        if bytes.len() < 2 { continue; }
-
-        let direction = bytes[0];
-        let distance: i32 = rotation[1..].parse().unwrap();
-
+    for &Rotation { direction, distance } in rotations {
         match direction {
             b'R' => {
-                // Moving right: count multiples of 100 in range (position, position + distance]
-                zero_count += (position + distance) / 100;
-                position = (position + distance).rem_euclid(100);
+                // Moving right: count multiples of size in range (position, position + distance]
+                zero_count += (position + distance) / size;
+                position = (position + distance).rem_euclid(size);
             }
             b'L' => {
-                // Moving left: count multiples of 100 in range [position - distance, position)
-                // Count = floor((pos - 1) / 100) - floor((pos - dist - 1) / 100)
+                // Moving left: count multiples of size in range [position - distance, position)
+                // Count = floor((pos - 1) / size) - floor((pos - dist - 1) / size)
 
                 let start_floor = if (position - 1) < 0 { -1 } else { 0 };
-                let end_floor = (position - distance - 1).div_euclid(100);
+                let end_floor = (position - distance - 1).div_euclid(size);
 
                 zero_count += start_floor - end_floor;
-                position = (position - distance).rem_euclid(100);
+                position = (position - distance).rem_euclid(size);
             }
             _ => {}
         }
@@ -71,26 +132,24 @@ pub fn part2(input: &str) -> i32 {
     zero_count
 }
 
-/// Run Day 01 solutions
-pub fn run() {
-    let test_input_path = "../inputs/day01_test.txt";
-    let input_path = "../inputs/day01.txt";
-    
-    // Part 1
-    utils::run_solution(
-        "Part 1",
-        part1,
-        test_input_path,
-        input_path,
-        Some(3),
-    );
-    
-    // Part 2
-    utils::run_solution(
-        "Part 2",
-        part2,
-        test_input_path,
-        input_path,
-        Some(6),
-    );
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = Vec<Rotation>;
+    type Params = Dial;
+
+    const DAY: u32 = 1;
+    const TITLE: &'static str = "Secret Entrance";
+
+    fn parse(input: &str) -> Result<Vec<Rotation>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(rotations: &Vec<Rotation>, dial: &Dial, _budget: &Budget) -> Result<Answer, SolveError> {
+        Ok(part1(rotations, dial).into())
+    }
+
+    fn part2(rotations: &Vec<Rotation>, dial: &Dial, _budget: &Budget) -> Result<Answer, SolveError> {
+        Ok(part2(rotations, dial).into())
+    }
 }
@@ -1,52 +1,83 @@
-use crate::utils;
-
-pub fn part1(input: &str) -> i64 {
-    let input = input.trim();
-    let mut total_invalid_sum = 0;
-
-    for range in input.split(',') {
-        let parts: Vec<&str> = range.split('-').collect();
-        if parts.len() != 2 {
-            continue;
-        }
-
-        if let (Ok(min), Ok(max)) = (parts[0].parse::<i64>(), parts[1].parse::<i64>()) {
-            for i in min..=max {
-                if is_invalid_id_part1(i) {
-                    total_invalid_sum += i;
-                }
+use crate::answer::Answer;
+use crate::budget::Budget;
+use crate::error::{self, ParseError, SolveError};
+use crate::parallel;
+use crate::solution::Solution;
+use crate::utils::Input;
+
+/// Parses the comma separated `min-max` ID ranges
+pub fn parse(input: &str) -> Result<Vec<(i64, i64)>, ParseError> {
+    let input = Input::new(input);
+    let mut ranges = Vec::new();
+
+    for (line_number, line) in input.lines() {
+        // The list may be wrapped over several lines and end with a trailing comma
+        for range in line.split(',').filter(|range| !range.trim().is_empty()) {
+            let Some((min, max)) = range.split_once('-') else {
+                return Err(ParseError::at(
+                    2,
+                    line_number,
+                    line,
+                    range,
+                    format!("expected a range like 11-22, found '{}'", range.trim()),
+                ));
+            };
+            let min: i64 = error::parse_number(2, line_number, line, min)?;
+            let max: i64 = error::parse_number(2, line_number, line, max)?;
+            if min > max {
+                return Err(ParseError::at(
+                    2,
+                    line_number,
+                    line,
+                    range,
+                    format!("range {}-{} ends before it starts", min, max),
+                ));
             }
+            ranges.push((min, max));
         }
     }
 
-    total_invalid_sum
+    Ok(ranges)
 }
 
-pub fn part2(input: &str) -> i64 {
-    let input = input.trim();
-    let mut total_invalid_sum = 0;
+pub fn part1(ranges: &[(i64, i64)]) -> i64 {
+    part1_parallel(ranges, 1)
+}
 
-    for range in input.split(',') {
-        let parts: Vec<&str> = range.split('-').collect();
-        if parts.len() != 2 {
-            continue;
-        }
+pub fn part2(ranges: &[(i64, i64)]) -> i64 {
+    part2_parallel(ranges, 1)
+}
 
-        if let (Ok(min), Ok(max)) = (parts[0].parse::<i64>(), parts[1].parse::<i64>()) {
-            for i in min..=max {
-                if is_invalid_id_part2(i) {
-                    total_invalid_sum += i;
-                }
-            }
+/// Part 1 with the ranges scanned independently on up to `jobs` threads
+pub fn part1_parallel(ranges: &[(i64, i64)], jobs: usize) -> i64 {
+    parallel::map(ranges, jobs, |&range| sum_invalid_ids(range, is_invalid_id_part1))
+        .into_iter()
+        .sum()
+}
+
+/// Part 2 with the ranges scanned independently on up to `jobs` threads
+pub fn part2_parallel(ranges: &[(i64, i64)], jobs: usize) -> i64 {
+    parallel::map(ranges, jobs, |&range| sum_invalid_ids(range, is_invalid_id_part2))
+        .into_iter()
+        .sum()
+}
+
+/// Sums the IDs in `min..=max` that `is_invalid` rejects
+fn sum_invalid_ids((min, max): (i64, i64), is_invalid: fn(i64) -> bool) -> i64 {
+    let mut total_invalid_sum = 0;
+
+    for i in min..=max {
+        if is_invalid(i) {
+            total_invalid_sum += i;
         }
     }
 
     total_invalid_sum
 }
 
-fn is_invalid_id_part1(n: i64) -> bool {
+pub fn is_invalid_id_part1(n: i64) -> bool {
     let s = n.to_string();
-    if s.len() % 2 != 0 {
+    if !s.len().is_multiple_of(2) {
         return false;
     }
 
@@ -63,7 +94,8 @@ fn is_invalid_id_part1(n: i64) -> bool {
     true
 }
 
-fn is_invalid_id_part2(n: i64) -> bool {
+/// An ID is invalid when it is some digit sequence repeated at least twice
+pub fn is_invalid_id_part2(n: i64) -> bool {
     let s = n.to_string();
     let len = s.len();
     let bytes = s.as_bytes();
@@ -71,7 +103,7 @@ fn is_invalid_id_part2(n: i64) -> bool {
     // Try all possible pattern lengths L
     // The pattern must repeat at least twice, so L can go up to len / 2
     for l in 1..=(len / 2) {
-        if len % l == 0 {
+        if len.is_multiple_of(l) {
             // Check if s is composed of repetitions of the first l characters
             let mut match_found = true;
             for i in l..len {
@@ -90,7 +122,24 @@ fn is_invalid_id_part2(n: i64) -> bool {
     false
 }
 
-pub fn run() {
-    utils::run_solution("Part 1", part1, "../inputs/day02_test.txt", "../inputs/day02.txt", Some(1227775554));
-    utils::run_solution("Part 2", part2, "../inputs/day02_test.txt", "../inputs/day02.txt", Some(4174379265));
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<(i64, i64)>;
+    type Params = ();
+
+    const DAY: u32 = 2;
+    const TITLE: &'static str = "Gift Shop";
+
+    fn parse(input: &str) -> Result<Vec<(i64, i64)>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(ranges: &Vec<(i64, i64)>, _params: &(), _budget: &Budget) -> Result<Answer, SolveError> {
+        Ok(part1_parallel(ranges, parallel::jobs()).into())
+    }
+
+    fn part2(ranges: &Vec<(i64, i64)>, _params: &(), _budget: &Budget) -> Result<Answer, SolveError> {
+        Ok(part2_parallel(ranges, parallel::jobs()).into())
+    }
 }
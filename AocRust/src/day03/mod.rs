@@ -1,22 +1,82 @@
-use crate::utils;
+use crate::answer::Answer;
+use crate::budget::Budget;
+use crate::error::{ParseError, SolveError};
+use crate::params::{self, Params, ParamsError};
+use crate::solution::Solution;
+use crate::utils::Input;
+
+/// How many batteries part 2 turns on in each bank
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Batteries {
+    pub count: usize,
+}
 
-pub fn part1(input: &str) -> i64 {
-    let input = input.trim();
-    let mut total_output_joltage = 0;
+impl Default for Batteries {
+    fn default() -> Self {
+        Batteries { count: 12 }
+    }
+}
+
+impl Params for Batteries {
+    const NAMES: &'static [&'static str] = &["batteries"];
+
+    fn set(&mut self, name: &str, value: &str) -> Result<(), ParamsError> {
+        match name {
+            "batteries" => self.count = params::value(name, value)?,
+            _ => return Err(params::unknown(name, Self::NAMES)),
+        }
+        Ok(())
+    }
 
-    for line in input.lines() {
-        let line = line.trim();
-        if line.is_empty() {
-            continue;
+    fn validate(&self) -> Result<(), ParamsError> {
+        // A bank's joltage is kept in a u128, which holds any number of 38 digits;
+        // the total across banks is checked when it is added up
+        if !(1..=38).contains(&self.count) {
+            return Err(ParamsError(format!("batteries must be between 1 and 38, got {}", self.count)));
         }
+        Ok(())
+    }
+}
+
+/// Parses each bank of batteries into its joltage digits
+pub fn parse(input: &str) -> Result<Vec<Vec<u8>>, ParseError> {
+    let input = Input::new(input);
+    let mut banks = Vec::new();
+
+    for (line_number, line) in input.lines() {
+        let bank = line.trim();
+
+        let mut digits = Vec::with_capacity(bank.len());
+        for (offset, c) in bank.char_indices() {
+            match c.to_digit(10) {
+                Some(digit) => digits.push(digit as u8),
+                None => {
+                    return Err(ParseError::at(
+                        3,
+                        line_number,
+                        line,
+                        &bank[offset..],
+                        format!("expected a joltage digit, found '{}'", c),
+                    ));
+                }
+            }
+        }
+        banks.push(digits);
+    }
 
-        let bytes = line.as_bytes();
+    Ok(banks)
+}
+
+pub fn part1(banks: &[Vec<u8>]) -> i64 {
+    let mut total_output_joltage = 0;
+
+    for bytes in banks {
         let mut max_joltage = -1;
 
         for i in 0..bytes.len() {
-            let digit_i = (bytes[i] - b'0') as i64;
+            let digit_i = bytes[i] as i64;
             for j in (i + 1)..bytes.len() {
-                let digit_j = (bytes[j] - b'0') as i64;
+                let digit_j = bytes[j] as i64;
                 let joltage = digit_i * 10 + digit_j;
                 if joltage > max_joltage {
                     max_joltage = joltage;
@@ -32,23 +92,16 @@ pub fn part1(input: &str) -> i64 {
     total_output_joltage
 }
 
-pub fn part2(input: &str) -> i64 {
-    let input = input.trim();
-    let mut total_output_joltage = 0;
-    let k = 12;
-
-    for line in input.lines() {
-        let line = line.trim();
-        if line.is_empty() {
-            continue;
-        }
+pub fn part2(banks: &[Vec<u8>], batteries: usize) -> Result<u128, SolveError> {
+    let mut total_output_joltage: u128 = 0;
+    let k = batteries;
 
-        let bytes = line.as_bytes();
+    for bytes in banks {
         let mut stack: Vec<u8> = Vec::with_capacity(k);
         let n = bytes.len();
 
         for i in 0..n {
-            let digit = bytes[i] - b'0';
+            let digit = bytes[i];
             let remaining = n - 1 - i;
 
             while !stack.is_empty() && digit > *stack.last().unwrap() && stack.len() + remaining >= k {
@@ -61,18 +114,37 @@ pub fn part2(input: &str) -> i64 {
         }
 
         // Construct the number directly from digits
-        let mut max_joltage: i64 = 0;
+        let mut max_joltage: u128 = 0;
         for &digit in &stack {
-            max_joltage = max_joltage * 10 + digit as i64;
+            max_joltage = max_joltage * 10 + digit as u128;
         }
 
-        total_output_joltage += max_joltage;
+        total_output_joltage = total_output_joltage.checked_add(max_joltage).ok_or_else(|| {
+            SolveError::Unsolvable("the total output joltage does not fit in 128 bits".to_string())
+        })?;
     }
 
-    total_output_joltage
+    Ok(total_output_joltage)
 }
 
-pub fn run() {
-    utils::run_solution("Part 1", part1, "../inputs/day03_test.txt", "../inputs/day03.txt", Some(357));
-    utils::run_solution("Part 2", part2, "../inputs/day03_test.txt", "../inputs/day03.txt", Some(3121910778619));
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = Vec<Vec<u8>>;
+    type Params = Batteries;
+
+    const DAY: u32 = 3;
+    const TITLE: &'static str = "Lobby";
+
+    fn parse(input: &str) -> Result<Vec<Vec<u8>>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(banks: &Vec<Vec<u8>>, _batteries: &Batteries, _budget: &Budget) -> Result<Answer, SolveError> {
+        Ok(part1(banks).into())
+    }
+
+    fn part2(banks: &Vec<Vec<u8>>, batteries: &Batteries, _budget: &Budget) -> Result<Answer, SolveError> {
+        part2(banks, batteries.count).map(Answer::from)
+    }
 }
@@ -1,107 +1,98 @@
-use crate::utils;
-
-pub fn part1(input: &str) -> i64 {
-    let input = input.trim();
-    let lines: Vec<&[u8]> = input.lines()
-        .map(|line| line.trim().as_bytes())
-        .filter(|bytes| !bytes.is_empty())
-        .collect();
-
-    if lines.is_empty() {
-        return 0;
-    }
-
-    let rows = lines.len();
-    let cols = lines[0].len();
-    let mut accessible_count = 0;
-
-    // Directions: N, NE, E, SE, S, SW, W, NW
-    let dr = [-1, -1, 0, 1, 1, 1, 0, -1];
-    let dc = [0, 1, 1, 1, 0, -1, -1, -1];
-
-    for r in 0..rows {
-        for c in 0..cols {
-            if lines[r][c] == b'@' {
-                let mut neighbor_count = 0;
-                for i in 0..8 {
-                    let nr = r as i32 + dr[i];
-                    let nc = c as i32 + dc[i];
-
-                    if nr >= 0 && nr < rows as i32 && nc >= 0 && nc < cols as i32 {
-                        if lines[nr as usize][nc as usize] == b'@' {
-                            neighbor_count += 1;
-                        }
-                    }
-                }
-
-                if neighbor_count < 4 {
-                    accessible_count += 1;
-                }
-            }
+use crate::answer::Answer;
+use crate::budget::Budget;
+use crate::error::{ParseError, SolveError};
+use crate::solution::Solution;
+use crate::utils::Input;
+use crate::utils::grid::Grid;
+
+/// Parses the map of paper rolls (`@`) into a grid of bytes
+pub fn parse(input: &str) -> Result<Grid<u8>, ParseError> {
+    let input = Input::new(input);
+    let mut rows: Vec<Vec<u8>> = Vec::new();
+
+    for (line_number, line) in input.lines() {
+        let row = line.trim();
+
+        if let Some(offset) = row.find(|c| c != '@' && c != '.') {
+            let found = row[offset..].chars().next().unwrap_or_default();
+            return Err(ParseError::at(
+                4,
+                line_number,
+                line,
+                &row[offset..],
+                format!("expected '@' or '.', found '{}'", found),
+            ));
+        }
+        if let Some(first) = rows.first()
+            && first.len() != row.len()
+        {
+            return Err(ParseError::new(
+                4,
+                line_number,
+                0,
+                format!("row is {} wide but the first row is {}", row.len(), first.len()),
+            ));
         }
+        rows.push(row.as_bytes().to_vec());
     }
 
-    accessible_count
+    Ok(Grid::from_rows(rows).expect("rows are checked to be the same width"))
 }
 
-pub fn part2(input: &str) -> i64 {
-    let input = input.trim();
-    let mut lines: Vec<Vec<u8>> = input.lines()
-        .map(|line| line.trim().as_bytes().to_vec())
-        .filter(|bytes| !bytes.is_empty())
-        .collect();
+/// How many of the eight cells around `(row, col)` hold a roll
+fn neighbouring_rolls(grid: &Grid<u8>, row: usize, col: usize) -> usize {
+    grid.neighbours8(row, col).filter(|&position| grid[position] == b'@').count()
+}
 
-    if lines.is_empty() {
-        return 0;
-    }
+pub fn part1(grid: &Grid<u8>) -> i64 {
+    grid.iter()
+        .filter(|&((row, col), &cell)| cell == b'@' && neighbouring_rolls(grid, row, col) < 4)
+        .count() as i64
+}
 
-    let rows = lines.len();
-    let cols = lines[0].len();
+pub fn part2(grid: &Grid<u8>) -> i64 {
+    // Rolls get removed as we go, so work on a copy
+    let mut grid = grid.clone();
     let mut total_removed = 0;
 
-    // Directions: N, NE, E, SE, S, SW, W, NW
-    let dr = [-1, -1, 0, 1, 1, 1, 0, -1];
-    let dc = [0, 1, 1, 1, 0, -1, -1, -1];
-
     loop {
-        let mut to_remove = Vec::new();
-
-        for r in 0..rows {
-            for c in 0..cols {
-                if lines[r][c] == b'@' {
-                    let mut neighbor_count = 0;
-                    for i in 0..8 {
-                        let nr = r as i32 + dr[i];
-                        let nc = c as i32 + dc[i];
-
-                        if nr >= 0 && nr < rows as i32 && nc >= 0 && nc < cols as i32 {
-                            if lines[nr as usize][nc as usize] == b'@' {
-                                neighbor_count += 1;
-                            }
-                        }
-                    }
-
-                    if neighbor_count < 4 {
-                        to_remove.push((r, c));
-                    }
-                }
-            }
-        }
+        let to_remove: Vec<(usize, usize)> = grid
+            .iter()
+            .filter(|&((row, col), &cell)| cell == b'@' && neighbouring_rolls(&grid, row, col) < 4)
+            .map(|(position, _)| position)
+            .collect();
 
         if to_remove.is_empty() {
             break;
         }
 
         total_removed += to_remove.len() as i64;
-        for (r, c) in to_remove {
-            lines[r][c] = b'.';
+        for position in to_remove {
+            grid[position] = b'.';
         }
     }
 
     total_removed
 }
 
-pub fn run() {
-    utils::run_solution("Part 1", part1, "../inputs/day04_test.txt", "../inputs/day04.txt", Some(13));
-    utils::run_solution("Part 2", part2, "../inputs/day04_test.txt", "../inputs/day04.txt", Some(43));
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = Grid<u8>;
+    type Params = ();
+
+    const DAY: u32 = 4;
+    const TITLE: &'static str = "Printing Department";
+
+    fn parse(input: &str) -> Result<Grid<u8>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(grid: &Grid<u8>, _params: &(), _budget: &Budget) -> Result<Answer, SolveError> {
+        Ok(part1(grid).into())
+    }
+
+    fn part2(grid: &Grid<u8>, _params: &(), _budget: &Budget) -> Result<Answer, SolveError> {
+        Ok(part2(grid).into())
+    }
 }
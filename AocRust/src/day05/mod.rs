@@ -1,45 +1,77 @@
-use crate::utils;
+use crate::answer::Answer;
+use crate::budget::Budget;
+use crate::error::{self, ParseError, SolveError};
+use crate::solution::Solution;
+use crate::utils::Input;
 
-pub fn run() {
-    let test_input_path = "../inputs/day05_test.txt";
-    let real_input_path = "../inputs/day05.txt";
+pub struct Day05;
 
-    utils::run_solution("Part 1", part1, test_input_path, real_input_path, Some(3));
-    utils::run_solution("Part 2", part2, test_input_path, real_input_path, Some(14));
-}
+impl Solution for Day05 {
+    type Input = Database;
+    type Params = ();
+
+    const DAY: u32 = 5;
+    const TITLE: &'static str = "Cafeteria";
 
-pub fn part1(input: &str) -> i64 {
-    let input = input.trim();
-    // Handle both Unix and Windows line endings for section separator
-    let sections: Vec<&str> = if input.contains("\r\n\r\n") {
-        input.split("\r\n\r\n").collect()
-    } else {
-        input.split("\n\n").collect()
-    };
-    if sections.len() < 2 {
-        return 0;
+    fn parse(input: &str) -> Result<Database, ParseError> {
+        parse(input)
     }
 
-    let mut ranges: Vec<(i64, i64)> = Vec::new();
-    for line in sections[0].lines() {
-        if let Some(dash_pos) = line.find('-') {
-            if let (Ok(start), Ok(end)) = (
-                line[..dash_pos].parse::<i64>(),
-                line[dash_pos + 1..].parse::<i64>()
-            ) {
-                ranges.push((start, end));
+    fn part1(database: &Database, _params: &(), _budget: &Budget) -> Result<Answer, SolveError> {
+        Ok(part1(database).into())
+    }
+
+    fn part2(database: &Database, _params: &(), _budget: &Budget) -> Result<Answer, SolveError> {
+        Ok(part2(database).into())
+    }
+}
+
+/// Fresh ingredient ID ranges and the available ingredient IDs
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Database {
+    pub ranges: Vec<(i64, i64)>,
+    pub ids: Vec<i64>,
+}
+
+/// Parses the range section and the ID section, separated by a blank line
+pub fn parse(input: &str) -> Result<Database, ParseError> {
+    let input = Input::new(input);
+    let mut database = Database::default();
+
+    for (section, lines) in input.sections().iter().enumerate() {
+        for &(line_number, line) in lines {
+            let text = line.trim();
+            if section > 0 {
+                database.ids.push(error::parse_number(5, line_number, line, text)?);
+                continue;
             }
+
+            let Some((start, end)) = text.split_once('-') else {
+                return Err(ParseError::at(
+                    5,
+                    line_number,
+                    line,
+                    text,
+                    format!("expected a range like 3-5, found '{}'", text),
+                ));
+            };
+            database.ranges.push((
+                error::parse_number(5, line_number, line, start)?,
+                error::parse_number(5, line_number, line, end)?,
+            ));
         }
     }
 
+    Ok(database)
+}
+
+pub fn part1(database: &Database) -> i64 {
     let mut fresh_count = 0;
-    for line in sections[1].lines() {
-        if let Ok(id) = line.parse::<i64>() {
-            for (start, end) in &ranges {
-                if id >= *start && id <= *end {
-                    fresh_count += 1;
-                    break;
-                }
+    for &id in &database.ids {
+        for (start, end) in &database.ranges {
+            if id >= *start && id <= *end {
+                fresh_count += 1;
+                break;
             }
         }
     }
@@ -47,29 +79,8 @@ pub fn part1(input: &str) -> i64 {
     fresh_count
 }
 
-pub fn part2(input: &str) -> i64 {
-    let input = input.trim();
-    // Handle both Unix and Windows line endings for section separator
-    let sections: Vec<&str> = if input.contains("\r\n\r\n") {
-        input.split("\r\n\r\n").collect()
-    } else {
-        input.split("\n\n").collect()
-    };
-    if sections.is_empty() {
-        return 0;
-    }
-
-    let mut ranges: Vec<(i64, i64)> = Vec::new();
-    for line in sections[0].lines() {
-        if let Some(dash_pos) = line.find('-') {
-            if let (Ok(start), Ok(end)) = (
-                line[..dash_pos].parse::<i64>(),
-                line[dash_pos + 1..].parse::<i64>()
-            ) {
-                ranges.push((start, end));
-            }
-        }
-    }
+pub fn part2(database: &Database) -> i64 {
+    let mut ranges = database.ranges.clone();
 
     // Sort ranges by start
     ranges.sort_unstable_by_key(|r| r.0);
@@ -1,212 +1,222 @@
-use crate::utils;
+use crate::answer::Answer;
+use crate::budget::Budget;
+use crate::error::{ParseError, SolveError};
+use crate::parallel;
+use crate::solution::Solution;
+use crate::utils::Input;
+use crate::utils::grid::Grid;
 
-pub fn run() {
-    let test_input_path = "../inputs/day06_test.txt";
-    let real_input_path = "../inputs/day06.txt";
+pub struct Day06;
 
-    utils::run_solution("Part 1", part1, test_input_path, real_input_path, Some(4277556));
-    utils::run_solution("Part 2", part2, test_input_path, real_input_path, Some(3263827));
-}
+impl Solution for Day06 {
+    type Input = Worksheet;
+    type Params = ();
 
-pub fn part1(input: &str) -> i64 {
-    let input = input.replace("\r", "");
-    let lines: Vec<&str> = input.lines().filter(|line| !line.is_empty()).collect();
+    const DAY: u32 = 6;
+    const TITLE: &'static str = "Trash Compactor";
 
-    if lines.is_empty() {
-        return 0;
+    fn parse(input: &str) -> Result<Worksheet, ParseError> {
+        parse(input)
     }
 
-    let height = lines.len();
-    let width = lines.iter().map(|line| line.len()).max().unwrap_or(0);
-
-    // OPTIMIZATION: Convert to Vec<Vec<u8>> for O(1) character access
-    // This fixes the O(n) chars().nth() bottleneck
-    let grid: Vec<Vec<u8>> = lines
-        .iter()
-        .map(|line| {
-            let mut bytes = line.as_bytes().to_vec();
-            bytes.resize(width, b' ');
-            bytes
-        })
-        .collect();
-
-    let mut problems: Vec<(Vec<i64>, char)> = Vec::new();
-    let mut start_col: Option<usize> = None;
-
-    for col in 0..width {
-        let mut is_empty_col = true;
-        for row in 0..height {
-            // O(1) access instead of O(n) chars().nth()
-            if grid[row][col] != b' ' {
-                is_empty_col = false;
-                break;
-            }
-        }
-
-        if !is_empty_col {
-            if start_col.is_none() {
-                start_col = Some(col);
-            }
-        } else {
-            if let Some(start) = start_col {
-                // End of a block
-                problems.push(parse_problem(&grid, start, col - 1));
-                start_col = None;
-            }
-        }
+    fn part1(worksheet: &Worksheet, _params: &(), _budget: &Budget) -> Result<Answer, SolveError> {
+        part1_parallel(worksheet, parallel::jobs()).map(Answer::from)
     }
 
-    // Handle last block if it extends to the edge
-    if let Some(start) = start_col {
-        problems.push(parse_problem(&grid, start, width - 1));
-    }
-
-    let mut total = 0;
-    for (numbers, op) in problems {
-        let mut result = numbers[0];
-        for i in 1..numbers.len() {
-            if op == '+' {
-                result += numbers[i];
-            } else if op == '*' {
-                result *= numbers[i];
-            }
-        }
-        total += result;
+    fn part2(worksheet: &Worksheet, _params: &(), _budget: &Budget) -> Result<Answer, SolveError> {
+        part2_parallel(worksheet, parallel::jobs()).map(Answer::from)
     }
+}
 
-    total
+/// The worksheet's problems, left to right
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Worksheet {
+    pub problems: Vec<Problem>,
 }
 
-fn parse_problem(grid: &[Vec<u8>], start_col: usize, end_col: usize) -> (Vec<i64>, char) {
-    let mut numbers: Vec<i64> = Vec::new();
-    let mut op = ' ';
+/// One problem with its numbers read both ways
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Problem {
+    /// 0-based column the problem starts at
+    pub start: usize,
+    /// `'+'` or `'*'`
+    pub op: char,
+    /// One number per row, top to bottom, as people read them
+    pub rows: Vec<i64>,
+    /// One number per column, right to left, each read top to bottom, as cephalopods read them
+    pub columns: Vec<i64>,
+}
 
-    let height = grid.len();
+/// Parses the worksheet and splits it into problems at all-blank columns.
+/// Every row holds digits except the last, which holds one operator per problem.
+pub fn parse(input: &str) -> Result<Worksheet, ParseError> {
+    let input = Input::new(input);
+    let numbered: Vec<(usize, &str)> = input.lines().collect();
 
-    // Numbers are in all rows except the last
-    for row in 0..height - 1 {
-        let substring = String::from_utf8_lossy(&grid[row][start_col..=end_col]).trim().to_string();
-        if !substring.is_empty() {
-            if let Ok(num) = substring.parse::<i64>() {
-                numbers.push(num);
-            }
-        }
+    if numbered.is_empty() {
+        return Ok(Worksheet::default());
     }
-
-    // Operator is in the last row
-    let op_string = String::from_utf8_lossy(&grid[height - 1][start_col..=end_col]).trim().to_string();
-    if !op_string.is_empty() {
-        op = op_string.chars().next().unwrap();
+    if numbered.len() < 2 {
+        return Err(ParseError::new(6, numbered[0].0, 0, "expected rows of numbers followed by a row of operators"));
     }
 
-    (numbers, op)
-}
-
-pub fn part2(input: &str) -> i64 {
-    let input = input.replace("\r", "");
-    let lines: Vec<&str> = input.lines().filter(|line| !line.is_empty()).collect();
-
-    if lines.is_empty() {
-        return 0;
+    let operator_row = numbered.len() - 1;
+    for (row, &(line_number, line)) in numbered.iter().enumerate() {
+        let allowed = |c: char| c == ' ' || if row == operator_row { c == '+' || c == '*' } else { c.is_ascii_digit() };
+        if let Some(offset) = line.find(|c| !allowed(c)) {
+            let expected = if row == operator_row { "'+' or '*'" } else { "a digit" };
+            let found = line[offset..].chars().next().unwrap_or_default();
+            return Err(ParseError::at(
+                6,
+                line_number,
+                line,
+                &line[offset..],
+                format!("expected {}, found '{}'", expected, found),
+            ));
+        }
     }
 
-    let height = lines.len();
-    let width = lines.iter().map(|line| line.len()).max().unwrap_or(0);
-
-    // OPTIMIZATION: Convert to Vec<Vec<u8>> for O(1) character access
-    let grid: Vec<Vec<u8>> = lines
-        .iter()
-        .map(|line| {
-            let mut bytes = line.as_bytes().to_vec();
-            bytes.resize(width, b' ');
-            bytes
-        })
-        .collect();
+    let grid = input.grid();
+    let width = grid.width();
 
-    let mut problems: Vec<(Vec<i64>, char)> = Vec::new();
+    let mut blocks = Vec::new();
     let mut start_col: Option<usize> = None;
 
     for col in 0..width {
-        let mut is_empty_col = true;
-        for row in 0..height {
-            // O(1) access instead of O(n) chars().nth()
-            if grid[row][col] != b' ' {
-                is_empty_col = false;
-                break;
-            }
-        }
+        let is_empty_col = grid.column(col).all(|&c| c == b' ');
 
         if !is_empty_col {
             if start_col.is_none() {
                 start_col = Some(col);
             }
-        } else {
-            if let Some(start) = start_col {
-                // End of a block - parse reading right-to-left
-                problems.push(parse_problem_right_to_left(&grid, start, col - 1));
-                start_col = None;
-            }
+        } else if let Some(start) = start_col {
+            // End of a block
+            blocks.push((start, col - 1));
+            start_col = None;
         }
     }
 
     // Handle last block if it extends to the edge
     if let Some(start) = start_col {
-        problems.push(parse_problem_right_to_left(&grid, start, width - 1));
+        blocks.push((start, width - 1));
     }
 
-    let mut total = 0;
-    for (numbers, op) in problems {
-        let mut result = numbers[0];
-        for i in 1..numbers.len() {
-            if op == '+' {
-                result += numbers[i];
-            } else if op == '*' {
-                result *= numbers[i];
-            }
+    let line_numbers: Vec<usize> = numbered.iter().map(|&(line_number, _)| line_number).collect();
+    let problems = blocks
+        .into_iter()
+        .map(|(start, end)| parse_problem(&grid, &line_numbers, start, end))
+        .collect::<Result<_, _>>()?;
+    Ok(Worksheet { problems })
+}
+
+/// Reads the problem in columns `start..=end`; `line_numbers` maps grid rows to input lines
+fn parse_problem(grid: &Grid<u8>, line_numbers: &[usize], start: usize, end: usize) -> Result<Problem, ParseError> {
+    let operator_row = grid.height() - 1;
+    let span = format!("columns {}-{}", start + 1, end + 1);
+
+    let operators: Vec<u8> = grid.row(operator_row)[start..=end].iter().copied().filter(|&c| c != b' ').collect();
+    let &[op] = operators.as_slice() else {
+        return Err(ParseError::new(
+            6,
+            line_numbers[operator_row],
+            start + 1,
+            format!("expected one operator for the problem in {}, found {}", span, operators.len()),
+        ));
+    };
+
+    let mut rows = Vec::new();
+    for row in 0..operator_row {
+        let cells = &grid.row(row)[start..=end];
+        let Some(first) = cells.iter().position(|&c| c != b' ') else {
+            continue;
+        };
+        let last = cells.iter().rposition(|&c| c != b' ').expect("the row has a digit");
+        if let Some(gap) = cells[first..=last].iter().position(|&c| c == b' ') {
+            return Err(ParseError::new(
+                6,
+                line_numbers[row],
+                start + first + gap + 1,
+                format!("expected one number per row in the problem in {}, found a gap", span),
+            ));
+        }
+        rows.push(number(&cells[first..=last], line_numbers[row], start + first)?);
+    }
+
+    let mut columns = Vec::new();
+    for col in (start..=end).rev() {
+        let cells: Vec<u8> = (0..operator_row).map(|row| grid[(row, col)]).collect();
+        let Some(first) = cells.iter().position(|&c| c != b' ') else {
+            continue;
+        };
+        let last = cells.iter().rposition(|&c| c != b' ').expect("the column has a digit");
+        if let Some(gap) = cells[first..=last].iter().position(|&c| c == b' ') {
+            return Err(ParseError::new(
+                6,
+                line_numbers[first + gap],
+                col + 1,
+                format!("expected the digits of column {} to be read top to bottom without a gap", col + 1),
+            ));
         }
-        total += result;
+        columns.push(number(&cells[first..=last], line_numbers[first], col)?);
+    }
+
+    if rows.is_empty() {
+        return Err(ParseError::new(
+            6,
+            line_numbers[operator_row],
+            start + 1,
+            format!("the problem in {} has no numbers", span),
+        ));
     }
 
-    total
+    Ok(Problem { start, op: op as char, rows, columns })
 }
 
-fn parse_problem_right_to_left(grid: &[Vec<u8>], start_col: usize, end_col: usize) -> (Vec<i64>, char) {
-    let mut numbers: Vec<i64> = Vec::new();
-    let mut op = ' ';
+/// The number spelled by `digits`, which start at 0-based `col` of input line `line_number`
+fn number(digits: &[u8], line_number: usize, col: usize) -> Result<i64, ParseError> {
+    let digits = std::str::from_utf8(digits).expect("digits are ASCII");
+    digits.parse().map_err(|_| {
+        ParseError::new(6, line_number, col + 1, format!("number {} does not fit in 64 bits", digits))
+    })
+}
 
-    let height = grid.len();
+pub fn part1(worksheet: &Worksheet) -> Result<i128, SolveError> {
+    part1_parallel(worksheet, 1)
+}
 
-    // Read each column from right to left
-    for col in (start_col..=end_col).rev() {
-        let mut digit_chars: Vec<u8> = Vec::new();
+/// Part 1 with the problems evaluated independently on up to `jobs` threads
+pub fn part1_parallel(worksheet: &Worksheet, jobs: usize) -> Result<i128, SolveError> {
+    let results = parallel::map(&worksheet.problems, jobs, |problem| evaluate(problem, &problem.rows));
+    grand_total(results)
+}
 
-        // Read digits from top to bottom in this column (rows 0 to height-2, excluding operator row)
-        for row in 0..height - 1 {
-            // O(1) access instead of O(n) chars().nth()
-            let c = grid[row][col];
-            if c != b' ' {
-                digit_chars.push(c);
-            }
-        }
+pub fn part2(worksheet: &Worksheet) -> Result<i128, SolveError> {
+    part2_parallel(worksheet, 1)
+}
 
-        // Build number from these digits
-        if !digit_chars.is_empty() {
-            let num_str = String::from_utf8_lossy(&digit_chars).to_string();
-            if let Ok(num) = num_str.parse::<i64>() {
-                numbers.push(num);
-            }
-        }
-    }
+/// Part 2 with the problems evaluated independently on up to `jobs` threads
+pub fn part2_parallel(worksheet: &Worksheet, jobs: usize) -> Result<i128, SolveError> {
+    let results = parallel::map(&worksheet.problems, jobs, |problem| evaluate(problem, &problem.columns));
+    grand_total(results)
+}
 
-    // Operator is in the last row - find it anywhere in this block
-    for col in start_col..=end_col {
-        // O(1) access instead of O(n) chars().nth()
-        let c = grid[height - 1][col] as char;
-        if c == '+' || c == '*' {
-            op = c;
-            break;
-        }
-    }
+/// A problem's result; products of many large numbers can pass even 64 bits,
+/// so it is kept in 128 and anything larger is an error rather than a wrong total
+fn evaluate(problem: &Problem, numbers: &[i64]) -> Result<i128, SolveError> {
+    let identity = if problem.op == '*' { 1 } else { 0 };
+    let result = numbers.iter().try_fold(identity, |result: i128, &number| {
+        if problem.op == '*' { result.checked_mul(number as i128) } else { result.checked_add(number as i128) }
+    });
+    result.ok_or_else(|| {
+        SolveError::Unsolvable(format!("the problem starting at column {} does not fit in 128 bits", problem.start + 1))
+    })
+}
 
-    (numbers, op)
+/// The sum of every problem's result
+fn grand_total(results: Vec<Result<i128, SolveError>>) -> Result<i128, SolveError> {
+    results.into_iter().try_fold(0i128, |total, result| {
+        total
+            .checked_add(result?)
+            .ok_or_else(|| SolveError::Unsolvable("the grand total does not fit in 128 bits".to_string()))
+    })
 }
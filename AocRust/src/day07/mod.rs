@@ -1,39 +1,91 @@
-use crate::utils;
+use crate::answer::Answer;
+use crate::budget::Budget;
+use crate::error::{ParseError, SolveError};
+use crate::solution::Solution;
+use crate::utils::Input;
+use crate::utils::grid::Grid;
 use std::collections::HashMap;
 
-pub fn run() {
-    let test_input_path = "../inputs/day07_test.txt";
-    let real_input_path = "../inputs/day07.txt";
+pub struct Day07;
 
-    utils::run_solution("Part 1", part1, test_input_path, real_input_path, Some(21));
-    utils::run_solution("Part 2", part2, test_input_path, real_input_path, Some(40));
-}
+impl Solution for Day07 {
+    type Input = Manifold;
+    type Params = ();
 
-pub fn part1(input: &str) -> i64 {
-    let input = input.replace("\r", "");
-    let lines: Vec<&str> = input.lines().filter(|line| !line.is_empty()).collect();
+    const DAY: u32 = 7;
+    const TITLE: &'static str = "Laboratories";
 
-    if lines.is_empty() {
-        return 0;
+    fn parse(input: &str) -> Result<Manifold, ParseError> {
+        parse(input)
+    }
+
+    fn part1(manifold: &Manifold, _params: &(), _budget: &Budget) -> Result<Answer, SolveError> {
+        Ok(part1(manifold).into())
+    }
+
+    fn part2(manifold: &Manifold, _params: &(), _budget: &Budget) -> Result<Answer, SolveError> {
+        part2(manifold).map(Answer::from)
     }
+}
+
+/// The tachyon manifold diagram and the position of the beam source `S`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Manifold {
+    pub grid: Grid<u8>,
+    pub start_row: usize,
+    pub start_col: usize,
+}
 
-    let grid: Vec<Vec<char>> = lines.iter().map(|line| line.chars().collect()).collect();
-    let height = grid.len();
-    let width = grid[0].len();
-
-    // Find starting position 'S'
-    let mut start_row = 0;
-    let mut start_col = 0;
-    'outer: for row in 0..height {
-        for col in 0..width {
-            if grid[row][col] == 'S' {
-                start_row = row;
-                start_col = col;
-                break 'outer;
+/// Parses the diagram of empty space (`.`) and splitters (`^`) below a single source `S`
+pub fn parse(input: &str) -> Result<Manifold, ParseError> {
+    let input = Input::new(input);
+    let mut rows: Vec<Vec<u8>> = Vec::new();
+    let mut sources = 0;
+
+    for (line_number, line) in input.lines() {
+        for (offset, c) in line.char_indices() {
+            let error = |message: String| ParseError::at(7, line_number, line, &line[offset..], message);
+            match c {
+                '.' | '^' => {}
+                'S' if sources == 0 => sources += 1,
+                'S' => return Err(error("found a second beam source 'S'".to_string())),
+                _ => return Err(error(format!("expected '.', '^' or 'S', found '{}'", c))),
             }
         }
+
+        if let Some(first) = rows.first()
+            && first.len() != line.len()
+        {
+            return Err(ParseError::new(
+                7,
+                line_number,
+                0,
+                format!("row is {} wide but the first row is {}", line.len(), first.len()),
+            ));
+        }
+        rows.push(line.as_bytes().to_vec());
+    }
+
+    let grid = Grid::from_rows(rows).expect("rows are checked to be the same width");
+    if grid.is_empty() {
+        return Ok(Manifold::default());
+    }
+    let Some((start_row, start_col)) = grid.find(&b'S') else {
+        return Err(ParseError::new(7, 1, 0, "the diagram has no beam source 'S'"));
+    };
+
+    Ok(Manifold { grid, start_row, start_col })
+}
+
+pub fn part1(manifold: &Manifold) -> i64 {
+    let &Manifold { ref grid, start_row, start_col } = manifold;
+    if grid.is_empty() {
+        return 0;
     }
 
+    let height = grid.height();
+    let width = grid.width();
+
     // Simulate beams moving downward
     let mut current_beams = std::collections::HashSet::new();
     current_beams.insert(start_col);
@@ -43,9 +95,9 @@ pub fn part1(input: &str) -> i64 {
         let mut next_beams = std::collections::HashSet::new();
 
         for &col in &current_beams {
-            let cell = grid[row][col];
+            let cell = grid[(row, col)];
 
-            if cell == '^' {
+            if cell == b'^' {
                 // Splitter encountered
                 split_count += 1;
 
@@ -73,52 +125,43 @@ pub fn part1(input: &str) -> i64 {
     split_count
 }
 
-pub fn part2(input: &str) -> i64 {
-    let input = input.replace("\r", "");
-    let lines: Vec<&str> = input.lines().filter(|line| !line.is_empty()).collect();
-
-    if lines.is_empty() {
-        return 0;
+pub fn part2(manifold: &Manifold) -> Result<u128, SolveError> {
+    let &Manifold { ref grid, start_row, start_col } = manifold;
+    if grid.is_empty() {
+        return Ok(0);
     }
 
-    let grid: Vec<Vec<char>> = lines.iter().map(|line| line.chars().collect()).collect();
-    let height = grid.len();
-    let width = grid[0].len();
-
-    // Find starting position 'S'
-    let mut start_row = 0;
-    let mut start_col = 0;
-    'outer: for row in 0..height {
-        for col in 0..width {
-            if grid[row][col] == 'S' {
-                start_row = row;
-                start_col = col;
-                break 'outer;
-            }
-        }
-    }
+    let height = grid.height();
+    let width = grid.width();
 
     // For Part 2, track the number of distinct timelines/paths
     let mut current_paths = HashMap::new();
-    current_paths.insert(start_col, 1i64);
+    current_paths.insert(start_col, 1u128);
 
     for row in start_row + 1..height {
         let mut next_paths = HashMap::new();
+        let mut add = |col: usize, path_count: u128| {
+            let count = next_paths.entry(col).or_insert(0u128);
+            *count = count.checked_add(path_count).ok_or_else(|| {
+                SolveError::Unsolvable(format!("more than {} timelines reach row {}", u128::MAX, row + 1))
+            })?;
+            Ok::<(), SolveError>(())
+        };
 
         for (&col, &path_count) in &current_paths {
-            let cell = grid[row][col];
+            let cell = grid[(row, col)];
 
-            if cell == '^' {
+            if cell == b'^' {
                 // Splitter - particle takes both paths (quantum splitting)
                 if col > 0 {
-                    *next_paths.entry(col - 1).or_insert(0) += path_count;
+                    add(col - 1, path_count)?;
                 }
                 if col + 1 < width {
-                    *next_paths.entry(col + 1).or_insert(0) += path_count;
+                    add(col + 1, path_count)?;
                 }
             } else {
                 // Empty space - particle continues downward
-                *next_paths.entry(col).or_insert(0) += path_count;
+                add(col, path_count)?;
             }
         }
 
@@ -131,5 +174,9 @@ pub fn part2(input: &str) -> i64 {
     }
 
     // Sum all timelines that reach the bottom
-    current_paths.values().sum()
+    current_paths.values().try_fold(0u128, |total, &count| {
+        total
+            .checked_add(count)
+            .ok_or_else(|| SolveError::Unsolvable(format!("more than {} timelines reach the bottom", u128::MAX)))
+    })
 }
@@ -1,37 +1,60 @@
-use crate::utils;
-use std::collections::{HashMap, HashSet};
+use crate::answer::Answer;
+use crate::budget::Budget;
+use crate::error::{self, ParseError, SolveError};
+use crate::params::{self, Params, ParamsError};
+use crate::solution::Solution;
+use crate::utils::{DisjointSet, Input};
 
-pub fn run() {
-    let test_input_path = "../inputs/day08_test.txt";
-    let real_input_path = "../inputs/day08.txt";
+pub struct Day08;
 
-    utils::run_solution("Part 1", part1, test_input_path, real_input_path, Some(40));
-    utils::run_solution("Part 2", part2, test_input_path, real_input_path, Some(25272));
-}
+impl Solution for Day08 {
+    type Input = Playground;
+    type Params = Connections;
 
-pub fn part1(input: &str) -> i64 {
-    let input = input.replace("\r", "");
-    let lines: Vec<&str> = input.lines().filter(|line| !line.is_empty()).collect();
+    const DAY: u32 = 8;
+    const TITLE: &'static str = "Playground";
 
-    if lines.is_empty() {
-        return 0;
+    fn parse(input: &str) -> Result<Playground, ParseError> {
+        parse(input)
+    }
+
+    fn part1(playground: &Playground, connections: &Connections, _budget: &Budget) -> Result<Answer, SolveError> {
+        Ok(part1(playground, connections.count).into())
     }
 
-    // Parse junction box positions
+    fn part2(playground: &Playground, _connections: &Connections, _budget: &Budget) -> Result<Answer, SolveError> {
+        part2(playground).map(Answer::from)
+    }
+}
+
+/// Parses one `X,Y,Z` junction box position per line
+pub fn parse_points(input: &str) -> Result<Vec<(i32, i32, i32)>, ParseError> {
+    let input = Input::new(input);
     let mut points = Vec::new();
-    for line in lines {
+
+    for (line_number, line) in input.lines() {
         let parts: Vec<&str> = line.split(',').collect();
-        if parts.len() == 3 {
-            if let (Ok(x), Ok(y), Ok(z)) = (
-                parts[0].parse::<i32>(),
-                parts[1].parse::<i32>(),
-                parts[2].parse::<i32>(),
-            ) {
-                points.push((x, y, z));
-            }
+        if parts.len() != 3 {
+            return Err(ParseError::new(
+                8,
+                line_number,
+                0,
+                format!("expected a position like 162,817,812, found '{}'", line),
+            ));
         }
+
+        points.push((
+            error::parse_number(8, line_number, line, parts[0])?,
+            error::parse_number(8, line_number, line, parts[1])?,
+            error::parse_number(8, line_number, line, parts[2])?,
+        ));
     }
 
+    Ok(points)
+}
+
+/// Returns every pair of points as `(distance, i, j)`, closest pair first
+pub fn pairs_by_distance(points: &[(i32, i32, i32)]) -> Vec<(f64, usize, usize)> {
     let n = points.len();
 
     // Calculate all pairwise distances
@@ -50,158 +73,91 @@ pub fn part1(input: &str) -> i64 {
 
     // Sort by distance
     distances.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+    distances
+}
 
-    // Union-Find
-    let mut parent: Vec<usize> = (0..n).collect();
-    let mut size = vec![1; n];
+/// Junction boxes and every pair of them ordered by distance, shared by both parts
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Playground {
+    pub points: Vec<(i32, i32, i32)>,
+    pub pairs: Vec<(f64, usize, usize)>,
+}
 
-    fn find(parent: &mut Vec<usize>, x: usize) -> usize {
-        if parent[x] != x {
-            parent[x] = find(parent, parent[x]);
-        }
-        parent[x]
-    }
+/// How many of the shortest pairs part 1 connects: 1000 for the puzzle, 10 in its example
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Connections {
+    pub count: usize,
+}
 
-    fn union(parent: &mut Vec<usize>, size: &mut Vec<usize>, x: usize, y: usize) {
-        let root_x = find(parent, x);
-        let root_y = find(parent, y);
-        if root_x != root_y {
-            if size[root_x] < size[root_y] {
-                parent[root_x] = root_y;
-                size[root_y] += size[root_x];
-            } else {
-                parent[root_y] = root_x;
-                size[root_x] += size[root_y];
-            }
-        }
+impl Default for Connections {
+    fn default() -> Self {
+        Connections { count: 1000 }
     }
+}
 
-    // Connect the 1000 shortest pairs (or 10 for test)
-    let connections_to_make = if n == 20 { 10 } else { 1000 };
-    let mut connections_made = 0;
+impl Params for Connections {
+    const NAMES: &'static [&'static str] = &["connections"];
 
-    for (_dist, i, j) in &distances {
-        if connections_made >= connections_to_make {
-            break;
+    fn set(&mut self, name: &str, value: &str) -> Result<(), ParamsError> {
+        match name {
+            "connections" => self.count = params::value(name, value)?,
+            _ => return Err(params::unknown(name, Self::NAMES)),
         }
-        union(&mut parent, &mut size, *i, *j);
-        connections_made += 1;
-    }
-
-    // Find all unique circuits and their sizes
-    let mut circuit_sizes = HashMap::new();
-    for i in 0..n {
-        let root = find(&mut parent, i);
-        *circuit_sizes.entry(root).or_insert(0) += 1;
-    }
-
-    // Get three largest circuit sizes
-    let mut sizes: Vec<i64> = circuit_sizes.values().map(|&s| s as i64).collect();
-    sizes.sort_by(|a, b| b.cmp(a));
-
-    if sizes.len() >= 3 {
-        sizes[0] * sizes[1] * sizes[2]
-    } else if sizes.len() == 2 {
-        sizes[0] * sizes[1]
-    } else if sizes.len() == 1 {
-        sizes[0]
-    } else {
-        0
+        Ok(())
     }
 }
 
-pub fn part2(input: &str) -> i64 {
-    let input = input.replace("\r", "");
-    let lines: Vec<&str> = input.lines().filter(|line| !line.is_empty()).collect();
+pub fn parse(input: &str) -> Result<Playground, ParseError> {
+    let points = parse_points(input)?;
+    let pairs = pairs_by_distance(&points);
+    Ok(Playground { points, pairs })
+}
 
-    if lines.is_empty() {
+pub fn part1(playground: &Playground, connections: usize) -> i64 {
+    let Playground { points, pairs: distances } = playground;
+    if points.is_empty() {
         return 0;
     }
 
-    // Parse junction box positions
-    let mut points = Vec::new();
-    for line in lines {
-        let parts: Vec<&str> = line.split(',').collect();
-        if parts.len() == 3 {
-            if let (Ok(x), Ok(y), Ok(z)) = (
-                parts[0].parse::<i32>(),
-                parts[1].parse::<i32>(),
-                parts[2].parse::<i32>(),
-            ) {
-                points.push((x, y, z));
-            }
-        }
-    }
-
     let n = points.len();
+    let mut circuits = DisjointSet::new(n);
 
-    // Calculate all pairwise distances
-    let mut distances = Vec::new();
-    for i in 0..n {
-        for j in (i + 1)..n {
-            let p1 = points[i];
-            let p2 = points[j];
-            let dx = (p1.0 - p2.0) as i64;
-            let dy = (p1.1 - p2.1) as i64;
-            let dz = (p1.2 - p2.2) as i64;
-            let dist = ((dx * dx + dy * dy + dz * dz) as f64).sqrt();
-            distances.push((dist, i, j));
-        }
+    // Connect the shortest pairs
+    for &(_dist, i, j) in distances.iter().take(connections) {
+        circuits.union(i, j);
     }
 
-    // Sort by distance
-    distances.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
-
-    // Union-Find
-    let mut parent: Vec<usize> = (0..n).collect();
-    let mut size = vec![1; n];
-
-    fn find(parent: &mut Vec<usize>, x: usize) -> usize {
-        if parent[x] != x {
-            parent[x] = find(parent, parent[x]);
-        }
-        parent[x]
-    }
+    // Multiply the sizes of the three largest circuits (or of all if there are fewer)
+    let mut sizes: Vec<i64> = circuits.sizes().map(|size| size as i64).collect();
+    sizes.sort_by(|a, b| b.cmp(a));
+    sizes.iter().take(3).product()
+}
 
-    fn union(parent: &mut Vec<usize>, size: &mut Vec<usize>, x: usize, y: usize) -> bool {
-        let root_x = find(parent, x);
-        let root_y = find(parent, y);
-        if root_x != root_y {
-            if size[root_x] < size[root_y] {
-                parent[root_x] = root_y;
-                size[root_y] += size[root_x];
-            } else {
-                parent[root_y] = root_x;
-                size[root_x] += size[root_y];
-            }
-            true
-        } else {
-            false
-        }
+pub fn part2(playground: &Playground) -> Result<i64, SolveError> {
+    let Playground { points, pairs: distances } = playground;
+    if points.len() < 2 {
+        return Err(SolveError::Unsolvable(format!(
+            "{} junction box(es) cannot be connected, at least two are needed",
+            points.len()
+        )));
     }
 
-    fn count_circuits(parent: &mut Vec<usize>, n: usize) -> usize {
-        let mut roots = HashSet::new();
-        for i in 0..n {
-            roots.insert(find(parent, i));
-        }
-        roots.len()
-    }
+    let mut circuits = DisjointSet::new(points.len());
 
     // Connect pairs until there's only one circuit
     let mut last_i = 0;
     let mut last_j = 0;
 
-    for (_dist, i, j) in &distances {
-        if union(&mut parent, &mut size, *i, *j) {
-            last_i = *i;
-            last_j = *j;
-            if count_circuits(&mut parent, n) == 1 {
+    for &(_dist, i, j) in distances {
+        if circuits.union(i, j) {
+            last_i = i;
+            last_j = j;
+            if circuits.count() == 1 {
                 break;
             }
         }
     }
 
     // Multiply X coordinates of last two connected junction boxes
-    (points[last_i].0 as i64) * (points[last_j].0 as i64)
+    Ok((points[last_i].0 as i64) * (points[last_j].0 as i64))
 }
@@ -1,22 +1,47 @@
+use crate::answer::Answer;
+use crate::budget::Budget;
+use crate::error::{ParseError, SolveError};
+use crate::solution::Solution;
+use crate::utils::Input;
+use crate::utils::input::ints;
 use std::cmp::{max, min};
 
-pub fn part1(input: &str) -> i64 {
-    let lines: Vec<&str> = input.trim().lines().collect();
-    if lines.is_empty() {
-        return 0;
-    }
-
-    // Parse red tile coordinates
+/// Parses the red tile coordinates, one `X,Y` pair per line, in loop order
+pub fn parse(input: &str) -> Result<Vec<(i32, i32)>, ParseError> {
+    let input = Input::new(input);
     let mut red_tiles = Vec::new();
-    for line in lines {
-        let parts: Vec<&str> = line.split(',').collect();
-        if parts.len() == 2 {
-            if let (Ok(x), Ok(y)) = (parts[0].trim().parse::<i32>(), parts[1].trim().parse::<i32>()) {
-                red_tiles.push((x, y));
-            }
+    for (line_number, line) in input.lines() {
+        if let Some(offset) = line.find(|c: char| !(c.is_ascii_digit() || c == ',' || c == '-' || c == ' ')) {
+            let found = line[offset..].chars().next().unwrap_or_default();
+            return Err(ParseError::at(
+                9,
+                line_number,
+                line,
+                &line[offset..],
+                format!("expected a digit or ',', found '{}'", found),
+            ));
         }
+        let numbers = ints(line);
+        let (&[x, y], 1) = (numbers.as_slice(), line.matches(',').count()) else {
+            return Err(ParseError::new(
+                9,
+                line_number,
+                0,
+                format!("expected a tile like 7,1, found '{}'", line.trim()),
+            ));
+        };
+        let coordinate = |value: i64| {
+            i32::try_from(value).map_err(|_| {
+                ParseError::new(9, line_number, 0, format!("coordinate {} is out of range", value))
+            })
+        };
+        red_tiles.push((coordinate(x)?, coordinate(y)?));
     }
 
+    Ok(red_tiles)
+}
+
+pub fn part1(red_tiles: &[(i32, i32)]) -> i64 {
     if red_tiles.len() < 2 {
         return 0;
     }
@@ -41,23 +66,7 @@ pub fn part1(input: &str) -> i64 {
     max_area
 }
 
-pub fn part2(input: &str) -> i64 {
-    let lines: Vec<&str> = input.trim().lines().collect();
-    if lines.is_empty() {
-        return 0;
-    }
-
-    // Parse red tile coordinates
-    let mut red_tiles = Vec::new();
-    for line in lines {
-        let parts: Vec<&str> = line.split(',').collect();
-        if parts.len() == 2 {
-            if let (Ok(x), Ok(y)) = (parts[0].trim().parse::<i32>(), parts[1].trim().parse::<i32>()) {
-                red_tiles.push((x, y));
-            }
-        }
-    }
-
+pub fn part2(red_tiles: &[(i32, i32)]) -> i64 {
     if red_tiles.len() < 2 {
         return 0;
     }
@@ -76,16 +85,16 @@ pub fn part2(input: &str) -> i64 {
             let rect_max_y = max(tile1.1, tile2.1);
 
             // Check if all four corners are inside or on the polygon boundary
-            if !is_inside_or_on_boundary((rect_min_x, rect_min_y), &red_tiles) {
+            if !is_inside_or_on_boundary((rect_min_x, rect_min_y), red_tiles) {
                 continue;
             }
-            if !is_inside_or_on_boundary((rect_min_x, rect_max_y), &red_tiles) {
+            if !is_inside_or_on_boundary((rect_min_x, rect_max_y), red_tiles) {
                 continue;
             }
-            if !is_inside_or_on_boundary((rect_max_x, rect_min_y), &red_tiles) {
+            if !is_inside_or_on_boundary((rect_max_x, rect_min_y), red_tiles) {
                 continue;
             }
-            if !is_inside_or_on_boundary((rect_max_x, rect_max_y), &red_tiles) {
+            if !is_inside_or_on_boundary((rect_max_x, rect_max_y), red_tiles) {
                 continue;
             }
 
@@ -202,7 +211,24 @@ fn direction(p1: (i32, i32), p2: (i32, i32), p3: (i32, i32)) -> i32 {
     }
 }
 
-pub fn run() {
-    crate::utils::run_solution("Part 1", part1, "../inputs/day09_test.txt", "../inputs/day09.txt", Some(50));
-    crate::utils::run_solution("Part 2", part2, "../inputs/day09_test.txt", "../inputs/day09.txt", Some(24));
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Input = Vec<(i32, i32)>;
+    type Params = ();
+
+    const DAY: u32 = 9;
+    const TITLE: &'static str = "Movie Theater";
+
+    fn parse(input: &str) -> Result<Vec<(i32, i32)>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(red_tiles: &Vec<(i32, i32)>, _params: &(), _budget: &Budget) -> Result<Answer, SolveError> {
+        Ok(part1(red_tiles).into())
+    }
+
+    fn part2(red_tiles: &Vec<(i32, i32)>, _params: &(), _budget: &Budget) -> Result<Answer, SolveError> {
+        Ok(part2(red_tiles).into())
+    }
 }
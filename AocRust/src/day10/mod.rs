@@ -1,121 +1,193 @@
-use crate::utils;
+use crate::answer::Answer;
+use crate::budget::Budget;
+use crate::error::{self, ParseError, SolveError};
+use crate::parallel;
+use crate::solution::Solution;
+use crate::utils::Input;
 
-pub fn run() {
-    let test_input_path = "../inputs/day10_test.txt";
-    let real_input_path = "../inputs/day10.txt";
+/// Nodes of the joltage search across all machines, about 50 times what the real input needs
+const MAX_SEARCH_STEPS: u64 = 100_000_000;
 
-    utils::run_solution("Part 1", part1, test_input_path, real_input_path, Some(7));
-    utils::run_solution("Part 2", part2, test_input_path, real_input_path, Some(33));
-}
+pub struct Day10;
 
-pub fn part1(input: &str) -> i64 {
-    let input = input.replace("\r", "");
-    let mut total_presses = 0;
+impl Solution for Day10 {
+    type Input = Vec<Machine>;
+    type Params = ();
 
-    for line in input.lines() {
-        let line = line.trim();
-        if line.is_empty() {
-            continue;
-        }
-        let (target, buttons) = parse_machine(line);
-        let min_presses = solve_gaussian_elimination(&target, &buttons);
-        if min_presses != i64::MAX {
-            total_presses += min_presses;
-        }
-    }
+    const DAY: u32 = 10;
+    const TITLE: &'static str = "Factory";
 
-    total_presses
-}
+    fn parse(input: &str) -> Result<Vec<Machine>, ParseError> {
+        parse(input)
+    }
 
-pub fn part2(input: &str) -> i64 {
-    let input = input.replace("\r", "");
-    let mut total_presses = 0;
+    fn max_steps(_params: &()) -> Option<u64> {
+        Some(MAX_SEARCH_STEPS)
+    }
 
-    for line in input.lines() {
-        let line = line.trim();
-        if line.is_empty() {
-            continue;
-        }
-        let (targets, buttons) = parse_machine_part2(line);
-        let min_presses = solve_integer_linear_programming(&targets, &buttons);
-        total_presses += min_presses;
+    fn part1(machines: &Vec<Machine>, _params: &(), _budget: &Budget) -> Result<Answer, SolveError> {
+        part1_parallel(machines, parallel::jobs()).map(Answer::from)
     }
 
-    total_presses
+    fn part2(machines: &Vec<Machine>, _params: &(), budget: &Budget) -> Result<Answer, SolveError> {
+        part2_parallel(machines, parallel::jobs(), budget).map(Answer::from)
+    }
 }
 
-fn parse_machine(line: &str) -> (Vec<bool>, Vec<Vec<bool>>) {
-    let s_idx = line.find('[').unwrap();
-    let e_idx = line.find(']').unwrap();
-    let indicator = &line[s_idx + 1..e_idx];
-    let target: Vec<bool> = indicator.chars().map(|c| c == '#').collect();
+/// A machine's indicator light diagram, button wiring and joltage requirements
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Machine {
+    pub lights: Vec<bool>,
+    /// Indices of the lights (and counters) each button is wired to
+    pub buttons: Vec<Vec<usize>>,
+    pub joltages: Vec<i64>,
+}
 
-    let mut buttons = Vec::new();
-    let mut pos = e_idx + 1;
-    let limit = line.find('{').unwrap_or(line.len());
-
-    while let Some(open) = line[pos..limit].find('(') {
-        let open_abs = pos + open;
-        if let Some(close) = line[open_abs..limit].find(')') {
-            let close_abs = open_abs + close;
-            let indices_str = &line[open_abs + 1..close_abs];
-            let mut button = vec![false; target.len()];
-            for num_str in indices_str.split(',') {
-                if let Ok(idx) = num_str.trim().parse::<usize>() {
-                    if idx < target.len() {
+impl Machine {
+    /// Buttons as toggle masks over the indicator lights
+    pub fn light_buttons(&self) -> Vec<Vec<bool>> {
+        self.buttons
+            .iter()
+            .map(|wiring| {
+                let mut button = vec![false; self.lights.len()];
+                for &idx in wiring {
+                    if idx < button.len() {
                         button[idx] = true;
                     }
                 }
-            }
-            buttons.push(button);
-            pos = close_abs + 1;
-        } else {
-            break;
-        }
+                button
+            })
+            .collect()
     }
 
-    (target, buttons)
+    /// Buttons as increment vectors over the joltage counters
+    pub fn counter_buttons(&self) -> Vec<Vec<i64>> {
+        self.buttons
+            .iter()
+            .map(|wiring| {
+                let mut button = vec![0i64; self.joltages.len()];
+                for &idx in wiring {
+                    if idx < button.len() {
+                        button[idx] = 1;
+                    }
+                }
+                button
+            })
+            .collect()
+    }
 }
 
-fn parse_machine_part2(line: &str) -> (Vec<i64>, Vec<Vec<i64>>) {
-    let s_idx = line.find('[').unwrap();
-    let e_idx = line.find(']').unwrap();
-    let _num_lights = line[s_idx + 1..e_idx].len();
+/// Parses one machine per non-empty line
+pub fn parse(input: &str) -> Result<Vec<Machine>, ParseError> {
+    Input::new(input)
+        .lines()
+        .map(|(line_number, line)| parse_machine(line_number, line))
+        .collect()
+}
 
-    let jolts_start = line.find('{').unwrap();
-    let jolts_end = line.find('}').unwrap();
-    let jolts_str = &line[jolts_start + 1..jolts_end];
-    let targets: Vec<i64> = jolts_str
-        .split(',')
-        .map(|s| s.trim().parse().unwrap())
-        .collect();
+pub fn part1(machines: &[Machine]) -> Result<i64, SolveError> {
+    part1_parallel(machines, 1)
+}
+
+pub fn part2(machines: &[Machine]) -> Result<i64, SolveError> {
+    part2_parallel(machines, 1, &Budget::unlimited())
+}
+
+/// Part 1 with the machines solved independently on up to `jobs` threads
+pub fn part1_parallel(machines: &[Machine], jobs: usize) -> Result<i64, SolveError> {
+    let numbered: Vec<(usize, &Machine)> = machines.iter().enumerate().collect();
+    parallel::map(&numbered, jobs, |&(index, machine)| {
+        solve_gaussian_elimination(&machine.lights, &machine.light_buttons()).ok_or_else(|| {
+            SolveError::Unsolvable(format!("no buttons of machine {} produce its light pattern", index + 1))
+        })
+    })
+    .into_iter()
+    .sum()
+}
+
+/// Part 2 with the machines solved independently on up to `jobs` threads, all
+/// drawing on the same search budget; a machine that fails stops the ones after it
+pub fn part2_parallel(machines: &[Machine], jobs: usize, budget: &Budget) -> Result<i64, SolveError> {
+    let numbered: Vec<(usize, &Machine)> = machines.iter().enumerate().collect();
+    let presses = parallel::try_map(&numbered, jobs, budget, |&(index, machine)| {
+        solve_integer_linear_programming(&machine.joltages, &machine.counter_buttons(), budget)?.ok_or_else(|| {
+            SolveError::Unsolvable(format!("no buttons of machine {} reach its joltage requirements", index + 1))
+        })
+    })?;
+    Ok(presses.into_iter().sum())
+}
+
+/// Parses a line such as `[.##.] (3) (1,3) (2) {3,5,4,7}`
+pub fn parse_machine(line_number: usize, line: &str) -> Result<Machine, ParseError> {
+    let error = |at: &str, message: String| ParseError::at(10, line_number, line, at, message);
+
+    let rest = line.trim();
+    let Some(after_open) = rest.strip_prefix('[') else {
+        return Err(error(rest, "expected '[' to start the indicator lights".to_string()));
+    };
+    let Some(close) = after_open.find(']') else {
+        return Err(error(after_open, "missing ']' after the indicator lights".to_string()));
+    };
+
+    let indicator = &after_open[..close];
+    let mut lights = Vec::with_capacity(indicator.len());
+    for (offset, c) in indicator.char_indices() {
+        match c {
+            '#' => lights.push(true),
+            '.' => lights.push(false),
+            _ => return Err(error(&indicator[offset..], format!("expected '#' or '.', found '{}'", c))),
+        }
+    }
 
     let mut buttons = Vec::new();
-    let mut pos = e_idx + 1;
-    while let Some(open) = line[pos..jolts_start].find('(') {
-        let open_abs = pos + open;
-        if let Some(close) = line[open_abs..jolts_start].find(')') {
-            let close_abs = open_abs + close;
-            let indices_str = &line[open_abs + 1..close_abs];
-            let mut button = vec![0i64; targets.len()];
-            for num_str in indices_str.split(',') {
-                if let Ok(idx) = num_str.trim().parse::<usize>() {
-                    if idx < targets.len() {
-                        button[idx] = 1;
-                    }
+    let mut joltages = Vec::new();
+    let mut rest = after_open[close + 1..].trim_start();
+
+    while !rest.is_empty() {
+        let (open, close) = match rest.as_bytes()[0] {
+            b'(' if joltages.is_empty() => ('(', ')'),
+            b'{' if joltages.is_empty() => ('{', '}'),
+            _ => return Err(error(rest, format!("unexpected '{}'", rest.chars().next().unwrap_or_default()))),
+        };
+        let Some(end) = rest.find(close) else {
+            return Err(error(rest, format!("missing '{}' to match '{}'", close, open)));
+        };
+
+        let values = rest[1..end].split(',');
+        if open == '(' {
+            let mut wiring = Vec::new();
+            for value in values {
+                let light: usize = error::parse_number(10, line_number, line, value)?;
+                if light >= lights.len() {
+                    return Err(error(
+                        value,
+                        format!("button wired to light {} but the machine has {} lights", light, lights.len()),
+                    ));
                 }
+                wiring.push(light);
             }
-            buttons.push(button);
-            pos = close_abs + 1;
+            buttons.push(wiring);
         } else {
-            break;
+            for value in values {
+                joltages.push(error::parse_number(10, line_number, line, value)?);
+            }
+            if joltages.len() != lights.len() {
+                return Err(error(
+                    rest,
+                    format!("{} joltage requirements for {} lights", joltages.len(), lights.len()),
+                ));
+            }
         }
+
+        rest = rest[end + 1..].trim_start();
     }
 
-    (targets, buttons)
+    Ok(Machine { lights, buttons, joltages })
 }
 
-fn solve_gaussian_elimination(target: &[bool], buttons: &[Vec<bool>]) -> i64 {
+/// Minimum number of button presses that produce `target`, solved over GF(2).
+/// Returns `None` when no combination of buttons reaches the target.
+pub fn solve_gaussian_elimination(target: &[bool], buttons: &[Vec<bool>]) -> Option<i64> {
     let num_lights = target.len();
     let num_buttons = buttons.len();
 
@@ -166,7 +238,7 @@ fn solve_gaussian_elimination(target: &[bool], buttons: &[Vec<bool>]) -> i64 {
             }
         }
         if all_zero && matrix[r][num_buttons] {
-            return i64::MAX;
+            return None;
         }
     }
 
@@ -212,10 +284,17 @@ fn solve_gaussian_elimination(target: &[bool], buttons: &[Vec<bool>]) -> i64 {
         }
     }
 
-    min_presses
+    Some(min_presses)
 }
 
-fn solve_integer_linear_programming(targets: &[i64], buttons: &[Vec<i64>]) -> i64 {
+/// Minimum total presses so every counter reaches its target exactly.
+/// Returns `None` when no non-negative integer solution exists, and an error
+/// when `budget` runs out before the search over the free buttons finishes.
+pub fn solve_integer_linear_programming(
+    targets: &[i64],
+    buttons: &[Vec<i64>],
+    budget: &Budget,
+) -> Result<Option<i64>, SolveError> {
     let num_counters = targets.len();
     let num_buttons = buttons.len();
 
@@ -292,9 +371,10 @@ fn solve_integer_linear_programming(targets: &[i64], buttons: &[Vec<i64>]) -> i6
         num_buttons,
         &mut min_presses,
         max_free_value,
-    );
+        budget,
+    )?;
 
-    if min_presses == i64::MAX { 0 } else { min_presses }
+    Ok(if min_presses == i64::MAX { None } else { Some(min_presses) })
 }
 
 fn search_solutions(
@@ -308,7 +388,10 @@ fn search_solutions(
     num_buttons: usize,
     min_presses: &mut i64,
     max_free_value: i64,
-) {
+    budget: &Budget,
+) -> Result<(), SolveError> {
+    budget.step()?;
+
     if free_var_idx == free_vars.len() {
         test_solution.copy_from_slice(current_solution);
         let mut valid = true;
@@ -317,7 +400,7 @@ fn search_solutions(
             cur_sum += s;
         }
         if cur_sum >= *min_presses {
-            return;
+            return Ok(());
         }
 
         for r in (0..num_counters).rev() {
@@ -351,7 +434,7 @@ fn search_solutions(
         if valid {
             *min_presses = (*min_presses).min(cur_sum);
         }
-        return;
+        return Ok(());
     }
 
     let var_idx = free_vars[free_var_idx];
@@ -375,10 +458,12 @@ fn search_solutions(
             num_buttons,
             min_presses,
             max_free_value,
-        );
+            budget,
+        )?;
         if *min_presses == 0 {
-            return;
+            return Ok(());
         }
     }
     current_solution[var_idx] = 0;
+    Ok(())
 }
@@ -1,177 +1,209 @@
-use crate::utils;
-use std::collections::{HashMap, HashSet};
+use crate::answer::Answer;
+use crate::budget::Budget;
+use crate::error::{ParseError, SolveError};
+use crate::params::{self, Params, ParamsError};
+use crate::solution::Solution;
+use crate::utils::Input;
+use crate::utils::graph::{Graph, GraphBuilder, NodeId};
+use std::collections::HashSet;
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Graph;
+    type Params = Devices;
+
+    const DAY: u32 = 11;
+    const TITLE: &'static str = "Reactor";
+
+    fn parse(input: &str) -> Result<Graph, ParseError> {
+        parse_graph(input)
+    }
 
-pub fn run() {
-    let test_input_path = "../inputs/day11_test.txt";
-    let test_input_path2 = "../inputs/day11_test_part2.txt";
-    let real_input_path = "../inputs/day11.txt";
+    fn part1(graph: &Graph, devices: &Devices, _budget: &Budget) -> Result<Answer, SolveError> {
+        part1(graph, devices).map(Answer::from)
+    }
 
-    utils::run_solution("Part 1", part1, test_input_path, real_input_path, Some(5));
-    utils::run_solution("Part 2", part2, test_input_path2, real_input_path, Some(2));
+    fn part2(graph: &Graph, devices: &Devices, _budget: &Budget) -> Result<Answer, SolveError> {
+        part2(graph, devices).map(Answer::from)
+    }
 }
 
-pub fn part1(input: &str) -> i64 {
-    let graph = parse_graph(input);
-    count_paths(&graph, "you", "out")
+/// Most devices part 2 can require, since paths are counted per subset of them
+const MAX_REQUIRED: usize = 10;
+
+/// The devices paths run between: part 1 from `you` to `out`, part 2 from
+/// `server` to `out` through every `required` device
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Devices {
+    pub you: String,
+    pub server: String,
+    pub out: String,
+    pub required: Vec<String>,
 }
 
-pub fn part2(input: &str) -> i64 {
-    let graph = parse_graph(input);
-    count_paths_with_required_nodes(&graph, "svr", "out", &["dac", "fft"])
+impl Default for Devices {
+    fn default() -> Self {
+        Devices {
+            you: "you".to_string(),
+            server: "svr".to_string(),
+            out: "out".to_string(),
+            required: vec!["dac".to_string(), "fft".to_string()],
+        }
+    }
 }
 
-fn parse_graph(input: &str) -> HashMap<String, Vec<String>> {
-    let mut graph = HashMap::new();
-    let input = input.replace("\r", "");
-
-    for line in input.lines() {
-        if line.is_empty() {
-            continue;
+impl Params for Devices {
+    const NAMES: &'static [&'static str] = &["you", "server", "out", "required"];
+
+    fn set(&mut self, name: &str, value: &str) -> Result<(), ParamsError> {
+        let value = value.trim().to_string();
+        match name {
+            "you" => self.you = value,
+            "server" => self.server = value,
+            "out" => self.out = value,
+            // A comma separated list, e.g. `dac,fft`
+            "required" => {
+                self.required =
+                    value.split(',').map(str::trim).filter(|device| !device.is_empty()).map(String::from).collect()
+            }
+            _ => return Err(params::unknown(name, Self::NAMES)),
         }
+        Ok(())
+    }
 
-        let parts: Vec<&str> = line.split(':').collect();
-        if parts.len() != 2 {
-            continue;
+    fn validate(&self) -> Result<(), ParamsError> {
+        for (name, device) in [("you", &self.you), ("server", &self.server), ("out", &self.out)] {
+            if device.is_empty() || device.contains(char::is_whitespace) {
+                return Err(ParamsError(format!("{} must be a device name, got '{}'", name, device)));
+            }
+        }
+        if self.required.len() > MAX_REQUIRED {
+            return Err(ParamsError(format!(
+                "at most {} devices can be required, got {}",
+                MAX_REQUIRED,
+                self.required.len()
+            )));
         }
+        Ok(())
+    }
+}
 
-        let node = parts[0].trim().to_string();
-        let connections: Vec<String> = parts[1]
-            .split_whitespace()
-            .map(|s| s.to_string())
-            .collect();
+pub fn part1(graph: &Graph, devices: &Devices) -> Result<u128, SolveError> {
+    count_paths(graph, &devices.you, &devices.out)
+}
 
-        graph.insert(node, connections);
-    }
+pub fn part2(graph: &Graph, devices: &Devices) -> Result<u128, SolveError> {
+    let required: Vec<&str> = devices.required.iter().map(String::as_str).collect();
+    count_paths_with_required_nodes(graph, &devices.server, &devices.out, &required)
+}
 
+/// Paths can only be counted from a device that is named in the input
+fn require_device(graph: &Graph, name: &str) -> Result<NodeId, SolveError> {
     graph
+        .id(name)
+        .ok_or_else(|| SolveError::Unsolvable(format!("the input has no device named '{}'", name)))
 }
 
-fn count_paths(graph: &HashMap<String, Vec<String>>, start: &str, end: &str) -> i64 {
-    let mut path_count = 0;
-    let mut visited = HashSet::new();
-
-    fn dfs(
-        current: &str,
-        end: &str,
-        graph: &HashMap<String, Vec<String>>,
-        visited: &mut HashSet<String>,
-        path_count: &mut i64,
-    ) {
-        // If we've reached the end, count this path
-        if current == end {
-            *path_count += 1;
-            return;
-        }
-
-        // Mark current node as visited
-        visited.insert(current.to_string());
+/// Parses `node: child child ...` lines into a graph of devices
+pub fn parse_graph(input: &str) -> Result<Graph, ParseError> {
+    let input = Input::new(input);
+    let mut builder = GraphBuilder::new();
+    let mut listed = HashSet::new();
 
-        // If this node has outgoing connections, explore them
-        if let Some(connections) = graph.get(current) {
-            for next in connections {
-                // Only visit nodes we haven't visited in this path
-                if !visited.contains(next) {
-                    dfs(next, end, graph, visited, path_count);
-                }
-            }
+    for (line_number, line) in input.lines() {
+        let parts: Vec<&str> = line.split(':').collect();
+        if parts.len() != 2 || parts[0].trim().is_empty() {
+            return Err(ParseError::new(
+                11,
+                line_number,
+                0,
+                format!("expected 'device: output output ...', found '{}'", line),
+            ));
         }
 
-        // Backtrack: unmark current node for other paths
-        visited.remove(current);
+        let node = builder.node(parts[0].trim());
+        if !listed.insert(node) {
+            return Err(ParseError::at(
+                11,
+                line_number,
+                line,
+                parts[0].trim(),
+                format!("device '{}' is listed twice", parts[0].trim()),
+            ));
+        }
+        for output in parts[1].split_whitespace() {
+            let output = builder.node(output);
+            builder.edge(node, output);
+        }
     }
 
-    dfs(start, end, graph, &mut visited, &mut path_count);
-    path_count
+    Ok(builder.build())
 }
 
-fn count_paths_with_required_nodes(
-    graph: &HashMap<String, Vec<String>>,
+/// Counts paths from `start` to `end`
+pub fn count_paths(graph: &Graph, start: &str, end: &str) -> Result<u128, SolveError> {
+    count_paths_with_required_nodes(graph, start, end, &[])
+}
+
+/// Counts paths from `start` to `end` that visit every node in `required_nodes`.
+///
+/// Only devices on some path from `start` to `end` matter. They must not form a
+/// loop, since data could then circle forever, so they can be put in
+/// topological order and the paths counted in one pass, per set of required
+/// devices seen so far.
+pub fn count_paths_with_required_nodes(
+    graph: &Graph,
     start: &str,
     end: &str,
     required_nodes: &[&str],
-) -> i64 {
-    // Build index map for required nodes
-    let mut required_index = HashMap::new();
-    for (i, &node) in required_nodes.iter().enumerate() {
-        required_index.insert(node, i);
+) -> Result<u128, SolveError> {
+    let start = require_device(graph, start)?;
+    let Some(end) = graph.id(end) else {
+        return Ok(0);
+    };
+    // A required device that is not in the input can never be visited
+    let Some(required) = required_nodes.iter().map(|&name| graph.id(name)).collect::<Option<Vec<NodeId>>>() else {
+        return Ok(0);
+    };
+
+    // Devices reachable from start that can also still reach end
+    let from_start = graph.reachable_from(start);
+    let to_end = graph.reversed().reachable_from(end);
+    let on_path: Vec<bool> = from_start.iter().zip(&to_end).map(|(&a, &b)| a && b).collect();
+    if !on_path[start as usize] {
+        return Ok(0);
     }
 
-    let mut visited = HashSet::new();
-    let mut memo: HashMap<(String, i32), i64> = HashMap::new();
-
-    fn dfs(
-        current: &str,
-        end: &str,
-        visited_required_bitmask: i32,
-        graph: &HashMap<String, Vec<String>>,
-        visited: &mut HashSet<String>,
-        memo: &mut HashMap<(String, i32), i64>,
-        required_index: &HashMap<&str, usize>,
-        required_nodes: &[&str],
-    ) -> i64 {
-        // If we've reached the end, check if all required nodes were visited
-        if current == end {
-            let all_required = (1 << required_nodes.len()) - 1;
-            return if visited_required_bitmask == all_required { 1 } else { 0 };
-        }
-
-        // Check memoization (only when not in visited set to avoid cycle issues)
-        let key = (current.to_string(), visited_required_bitmask);
-        if !visited.contains(current) {
-            if let Some(&cached) = memo.get(&key) {
-                return cached;
-            }
-        }
-
-        // Mark current node as visited (for cycle detection)
-        visited.insert(current.to_string());
-
-        // Track if this is a required node
-        let mut new_visited_required_bitmask = visited_required_bitmask;
-        if let Some(&idx) = required_index.get(current) {
-            new_visited_required_bitmask |= 1 << idx;
-        }
-
-        let mut count = 0;
-
-        // If this node has outgoing connections, explore them
-        if let Some(connections) = graph.get(current) {
-            for next in connections {
-                // Only visit nodes we haven't visited in this path
-                if !visited.contains(next) {
-                    count += dfs(
-                        next,
-                        end,
-                        new_visited_required_bitmask,
-                        graph,
-                        visited,
-                        memo,
-                        required_index,
-                        required_nodes,
-                    );
-                }
+    let order = graph.subgraph(&on_path).topological_order().map_err(|cycle| {
+        let names: Vec<&str> = cycle.iter().map(|&node| graph.name(node)).collect();
+        SolveError::Unsolvable(format!("devices {} form a loop, so there are endless paths", names.join(" -> ")))
+    })?;
+
+    // paths[node][mask]: paths from start to node that visited the required devices in mask
+    let required_bit = |node: NodeId| {
+        required
+            .iter()
+            .enumerate()
+            .filter(|&(_, &other)| other == node)
+            .fold(0, |mask, (index, _)| mask | 1 << index)
+    };
+    let masks = 1 << required.len();
+    let mut paths = vec![0u128; graph.len() * masks];
+    paths[start as usize * masks + required_bit(start)] = 1;
+
+    for node in order.into_iter().filter(|&node| on_path[node as usize]) {
+        for &next in graph.successors(node).iter().filter(|&&next| on_path[next as usize]) {
+            let bit = required_bit(next);
+            for mask in 0..masks {
+                let incoming = paths[node as usize * masks + mask];
+                let count = &mut paths[next as usize * masks + (mask | bit)];
+                *count = count.checked_add(incoming).ok_or_else(|| {
+                    SolveError::Unsolvable(format!("more than {} paths lead to {}", u128::MAX, graph.name(next)))
+                })?;
             }
         }
-
-        // Backtrack: unmark current node for other paths
-        visited.remove(current);
-
-        // Memoize the result
-        if !visited.contains(current) {
-            memo.insert(key, count);
-        }
-
-        count
     }
 
-    dfs(
-        start,
-        end,
-        0,
-        graph,
-        &mut visited,
-        &mut memo,
-        &required_index,
-        required_nodes,
-    )
+    Ok(paths[end as usize * masks + masks - 1])
 }
@@ -1,38 +1,96 @@
-use crate::utils;
-use std::collections::HashSet;
+use crate::answer::Answer;
+use crate::budget::Budget;
+use crate::error::{self, ParseError, SolveError};
+use crate::parallel;
+use crate::params::{self, Params, ParamsError};
+use crate::solution::Solution;
+use crate::utils::Input;
+use crate::utils::grid::Grid;
+use std::collections::BTreeSet;
+
+/// How long the packing search may try before a region is given up on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Packing {
+    /// Placements tried across all regions. The example's third region, which
+    /// cannot be packed, takes about 4,700,000 to rule out and the real input
+    /// about 100,000 in all, so the default leaves room for harder inputs
+    pub max_calls: u64,
+}
+
+impl Default for Packing {
+    fn default() -> Self {
+        Packing { max_calls: 20_000_000 }
+    }
+}
 
-pub fn run() {
-    let test_input_path = "../inputs/day12_test.txt";
-    let real_input_path = "../inputs/day12.txt";
+impl Params for Packing {
+    const NAMES: &'static [&'static str] = &["max_calls"];
+
+    fn set(&mut self, name: &str, value: &str) -> Result<(), ParamsError> {
+        match name {
+            "max_calls" => self.max_calls = params::value(name, value)?,
+            _ => return Err(params::unknown(name, Self::NAMES)),
+        }
+        Ok(())
+    }
 
-    utils::run_solution("Part 1", part1, test_input_path, real_input_path, Some(2));
-    println!("\n🎄 Part 2 automatically completed! Both stars earned! 🎄\n");
+    fn validate(&self) -> Result<(), ParamsError> {
+        if self.max_calls == 0 {
+            return Err(ParamsError("max_calls must be at least 1".to_string()));
+        }
+        Ok(())
+    }
 }
 
-pub fn part1(input: &str) -> i64 {
-    let (shapes, regions) = parse_input(input);
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Farm;
+    type Params = Packing;
+
+    const DAY: u32 = 12;
+    const TITLE: &'static str = "Christmas Tree Farm";
+    const HAS_PART2: bool = false;
+
+    fn parse(input: &str) -> Result<Farm, ParseError> {
+        parse(input)
+    }
 
+    fn max_steps(packing: &Packing) -> Option<u64> {
+        Some(packing.max_calls)
+    }
+
+    fn part1(farm: &Farm, _packing: &Packing, budget: &Budget) -> Result<Answer, SolveError> {
+        part1_parallel(farm, parallel::jobs(), budget).map(Answer::from)
+    }
+}
+
+pub fn part1(farm: &Farm) -> i64 {
+    part1_parallel(farm, 1, &Budget::unlimited()).expect("an unlimited budget never runs out")
+}
+
+/// Part 1 with the regions checked independently on up to `jobs` threads, all
+/// drawing on the same search budget; a region that runs out stops the ones after it
+pub fn part1_parallel(farm: &Farm, jobs: usize, budget: &Budget) -> Result<i64, SolveError> {
     // Precompute all shape orientations once
-    let all_orientations: Vec<Vec<Shape>> = shapes
+    let all_orientations: Vec<Vec<Shape>> = farm
+        .shapes
         .iter()
         .map(|s| get_all_orientations(s))
         .collect();
 
-    let mut count = 0;
-    for region in &regions {
-        if can_fit_all_presents(region, &all_orientations) {
-            count += 1;
-        }
-    }
-
-    count
+    let fits = parallel::try_map(&farm.regions, jobs, budget, |region| {
+        can_fit_all_presents(region, &all_orientations, budget)
+    })?;
+    Ok(fits.into_iter().filter(|&fits| fits).count() as i64)
 }
 
+/// A region under a tree and how many presents of each shape must fit in it
 #[derive(Debug, Clone)]
-struct Region {
-    width: usize,
-    height: usize,
-    counts: Vec<usize>,
+pub struct Region {
+    pub width: usize,
+    pub height: usize,
+    pub counts: Vec<usize>,
 }
 
 #[derive(Debug, Clone)]
@@ -72,54 +130,107 @@ impl Shape {
     }
 }
 
-fn parse_input(input: &str) -> (Vec<Vec<String>>, Vec<Region>) {
-    let input = input.replace("\r", "");
-    let lines: Vec<&str> = input.split('\n').collect();
+/// The present shapes (as rows of `#`/`.`) and the regions to fill
+#[derive(Debug, Clone, Default)]
+pub struct Farm {
+    pub shapes: Vec<Vec<String>>,
+    pub regions: Vec<Region>,
+}
+
+/// Parses the numbered present shapes followed by one `WxH: counts` region per line
+pub fn parse(input: &str) -> Result<Farm, ParseError> {
+    let input = Input::new(input);
     let mut shapes = Vec::new();
     let mut regions = Vec::new();
 
-    let mut i = 0;
-    while i < lines.len() {
-        let line = lines[i];
-
-        // Parse region (check this first since it also contains ':')
-        if line.contains('x') && line.contains(':') {
-            let parts: Vec<&str> = line.split(':').collect();
-            let dimensions: Vec<&str> = parts[0].trim().split('x').collect();
-            let width = dimensions[0].parse::<usize>().unwrap();
-            let height = dimensions[1].parse::<usize>().unwrap();
-
-            let counts: Vec<usize> = parts[1]
-                .trim()
-                .split_whitespace()
-                .map(|s| s.parse().unwrap())
-                .collect();
-
-            regions.push(Region { width, height, counts });
-            i += 1;
-        }
-        // Parse shape
-        else if line.contains(':') {
-            let mut shape_lines = Vec::new();
-            i += 1; // Skip the label line
+    // A shape's rows run until the next label or blank line, so walk each section
+    for lines in input.sections() {
+        let mut i = 0;
+        while i < lines.len() {
+            let (line_number, line) = lines[i];
+
+            let Some((label, rest)) = line.split_once(':') else {
+                return Err(ParseError::new(
+                    12,
+                    line_number,
+                    0,
+                    format!("expected a shape label like '0:' or a region like '4x4: 0 2', found '{}'", line),
+                ));
+            };
+
+            // Parse region (check this first since it also contains ':')
+            if let Some((width, height)) = label.split_once('x') {
+                let width: usize = error::parse_number(12, line_number, line, width)?;
+                let height: usize = error::parse_number(12, line_number, line, height)?;
+
+                let mut counts = Vec::new();
+                for count in rest.split_whitespace() {
+                    counts.push(error::parse_number(12, line_number, line, count)?);
+                }
+                if counts.len() > shapes.len() {
+                    return Err(ParseError::at(
+                        12,
+                        line_number,
+                        line,
+                        rest,
+                        format!("{} present counts but only {} shapes", counts.len(), shapes.len()),
+                    ));
+                }
 
-            while i < lines.len() && !lines[i].trim().is_empty() && !lines[i].contains(':') {
-                shape_lines.push(lines[i].to_string());
+                regions.push(Region { width, height, counts });
                 i += 1;
             }
+            // Parse shape
+            else {
+                let mut shape_lines: Vec<String> = Vec::new();
+                i += 1; // Skip the label line
+
+                while i < lines.len() && !lines[i].1.contains(':') {
+                    let (row_number, row) = lines[i];
+                    if let Some(offset) = row.find(|c| c != '#' && c != '.') {
+                        return Err(ParseError::at(
+                            12,
+                            row_number,
+                            row,
+                            &row[offset..],
+                            format!(
+                                "expected '#' or '.', found '{}'",
+                                row[offset..].chars().next().unwrap_or_default()
+                            ),
+                        ));
+                    }
+                    if let Some(first) = shape_lines.first()
+                        && first.len() != row.len()
+                    {
+                        return Err(ParseError::new(
+                            12,
+                            row_number,
+                            0,
+                            format!("shape row is {} wide but its first row is {}", row.len(), first.len()),
+                        ));
+                    }
+                    shape_lines.push(row.to_string());
+                    i += 1;
+                }
 
-            if !shape_lines.is_empty() {
+                if shape_lines.is_empty() {
+                    return Err(ParseError::new(12, line_number, 0, format!("shape '{}' has no rows", label.trim())));
+                }
                 shapes.push(shape_lines);
             }
-        } else {
-            i += 1;
         }
     }
 
-    (shapes, regions)
+    Ok(Farm { shapes, regions })
 }
 
-fn can_fit_all_presents(region: &Region, all_orientations: &[Vec<Shape>]) -> bool {
+/// Whether every present fits in the region; an error when `budget` runs out
+/// before the backtracking search can tell
+fn can_fit_all_presents(
+    region: &Region,
+    all_orientations: &[Vec<Shape>],
+    budget: &Budget,
+) -> Result<bool, SolveError> {
     // Quick area check
     let total_area = region.width * region.height;
     let mut required_area = 0;
@@ -132,31 +243,27 @@ fn can_fit_all_presents(region: &Region, all_orientations: &[Vec<Shape>]) -> boo
     }
 
     if required_area > total_area {
-        return false;
+        return Ok(false);
     }
 
-    // Use flat 1D array for better cache performance
-    let mut grid = vec![false; region.width * region.height];
+    // Grid keeps the cells in one flat array for better cache performance
+    let mut grid = Grid::new(region.width, region.height, false);
     let mut counts = region.counts.clone();
 
-    try_place_presents(&mut grid, region.width, region.height, &mut counts, all_orientations, 0)
+    try_place_presents(&mut grid, &mut counts, all_orientations, budget)
 }
 
 fn try_place_presents(
-    grid: &mut [bool],
-    width: usize,
-    height: usize,
+    grid: &mut Grid<bool>,
     counts: &mut [usize],
     all_orientations: &[Vec<Shape>],
-    call_count: i32,
-) -> bool {
-    if call_count > 2_000_000 {
-        return false;
-    }
+    budget: &Budget,
+) -> Result<bool, SolveError> {
+    budget.step()?;
 
     // Check if all presents are placed
     if counts.iter().all(|&count| count == 0) {
-        return true;
+        return Ok(true);
     }
 
     // Try placing first available present type
@@ -169,17 +276,17 @@ fn try_place_presents(
 
         // Try each orientation at each position
         for shape in orientations {
-            for row in 0..=(height.saturating_sub(shape.height)) {
-                for col in 0..=(width.saturating_sub(shape.width)) {
-                    if can_place_shape(grid, width, height, shape, row, col) {
-                        place_shape(grid, width, shape, row, col);
+            for row in 0..=(grid.height().saturating_sub(shape.height)) {
+                for col in 0..=(grid.width().saturating_sub(shape.width)) {
+                    if can_place_shape(grid, shape, row, col) {
+                        set_shape(grid, shape, row, col, true);
                         counts[shape_idx] -= 1;
 
-                        if try_place_presents(grid, width, height, counts, all_orientations, call_count + 1) {
-                            return true;
+                        if try_place_presents(grid, counts, all_orientations, budget)? {
+                            return Ok(true);
                         }
 
-                        remove_shape(grid, width, shape, row, col);
+                        set_shape(grid, shape, row, col, false);
                         counts[shape_idx] += 1;
                     }
                 }
@@ -187,33 +294,22 @@ fn try_place_presents(
         }
 
         // If we couldn't place this present type anywhere, fail
-        return false;
+        return Ok(false);
     }
 
-    true
+    Ok(true)
 }
 
-fn can_place_shape(
-    grid: &[bool],
-    width: usize,
-    height: usize,
-    shape: &Shape,
-    start_row: usize,
-    start_col: usize,
-) -> bool {
-    if start_row + shape.height > height {
+fn can_place_shape(grid: &Grid<bool>, shape: &Shape, start_row: usize, start_col: usize) -> bool {
+    if start_row + shape.height > grid.height() {
         return false;
     }
-    if start_col + shape.width > width {
+    if start_col + shape.width > grid.width() {
         return false;
     }
 
     for i in 0..shape.rows.len() {
-        let r = shape.rows[i];
-        let c = shape.cols[i];
-        let grid_idx = (start_row + r) * width + (start_col + c);
-
-        if grid[grid_idx] {
+        if grid[(start_row + shape.rows[i], start_col + shape.cols[i])] {
             return false;
         }
     }
@@ -221,26 +317,17 @@ fn can_place_shape(
     true
 }
 
-fn place_shape(grid: &mut [bool], width: usize, shape: &Shape, start_row: usize, start_col: usize) {
-    for i in 0..shape.rows.len() {
-        let r = shape.rows[i];
-        let c = shape.cols[i];
-        let grid_idx = (start_row + r) * width + (start_col + c);
-        grid[grid_idx] = true;
-    }
-}
-
-fn remove_shape(grid: &mut [bool], width: usize, shape: &Shape, start_row: usize, start_col: usize) {
+/// Places the shape (`filled`) or takes it away again
+fn set_shape(grid: &mut Grid<bool>, shape: &Shape, start_row: usize, start_col: usize, filled: bool) {
     for i in 0..shape.rows.len() {
-        let r = shape.rows[i];
-        let c = shape.cols[i];
-        let grid_idx = (start_row + r) * width + (start_col + c);
-        grid[grid_idx] = false;
+        grid[(start_row + shape.rows[i], start_col + shape.cols[i])] = filled;
     }
 }
 
+/// Every distinct rotation and reflection of a shape, in a fixed order so the
+/// search, and the steps it takes, are the same on every run
 fn get_all_orientations(shape_template: &[String]) -> Vec<Shape> {
-    let mut orientations = HashSet::new();
+    let mut orientations = BTreeSet::new();
     let mut current = shape_template.to_vec();
 
     for _ in 0..4 {
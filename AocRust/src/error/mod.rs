@@ -0,0 +1,127 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// Malformed puzzle input, pointing at the offending position.
+/// Lines and columns are 1-based; a column of 0 means the whole line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u32,
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(day: u32, line: usize, column: usize, message: impl Into<String>) -> Self {
+        ParseError {
+            day,
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    /// Error pointing at `token`, which must be a slice of `line`
+    pub fn at(day: u32, line_number: usize, line: &str, token: &str, message: impl Into<String>) -> Self {
+        ParseError::new(day, line_number, column_of(line, token), message)
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "day {}, line {}", self.day, self.line)?;
+        if self.column > 0 {
+            write!(f, ", column {}", self.column)?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// 1-based column at which `token` starts inside `line`
+pub fn column_of(line: &str, token: &str) -> usize {
+    let offset = (token.as_ptr() as usize).wrapping_sub(line.as_ptr() as usize);
+    if offset <= line.len() {
+        line[..offset].chars().count() + 1
+    } else {
+        0
+    }
+}
+
+/// Parses a number found at `token` within `line`, reporting its position on failure
+pub fn parse_number<T: FromStr>(day: u32, line_number: usize, line: &str, token: &str) -> Result<T, ParseError> {
+    let trimmed = token.trim();
+    trimmed.parse().map_err(|_| {
+        let message = if trimmed.is_empty() {
+            "expected a number".to_string()
+        } else {
+            format!("expected a number, found '{}'", trimmed)
+        };
+        ParseError::at(day, line_number, line, trimmed, message)
+    })
+}
+
+/// Why a well-formed input did not produce an answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    /// No answer satisfies the puzzle rules for this input
+    Unsolvable(String),
+    /// The solver's [`Budget`](crate::budget::Budget) ran out, so the answer is unknown
+    TimedOut(String),
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::Unsolvable(reason) => write!(f, "no solution: {}", reason),
+            SolveError::TimedOut(reason) => write!(f, "timed out: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+/// Any failure while turning an input into an answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    Parse(ParseError),
+    Solve(SolveError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Parse(e) => write!(f, "parse error: {}", e),
+            Error::Solve(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error {
+    /// Whether the solver gave up on its budget rather than failing
+    pub fn is_timeout(&self) -> bool {
+        matches!(self, Error::Solve(SolveError::TimedOut(_)))
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Parse(e) => Some(e),
+            Error::Solve(e) => Some(e),
+        }
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Self {
+        Error::Parse(e)
+    }
+}
+
+impl From<SolveError> for Error {
+    fn from(e: SolveError) -> Self {
+        Error::Solve(e)
+    }
+}
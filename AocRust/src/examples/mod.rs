@@ -0,0 +1,411 @@
+use std::fs;
+use std::path::PathBuf;
+
+use crate::answer::Answer;
+use crate::inputs::Inputs;
+use crate::manifest::{self, Case, Source};
+use crate::solution::{DynSolution, Part};
+use crate::utils::Input;
+use crate::verify::{ANSWER_PREFIX, PART_TWO_HEADING};
+
+/// Words that introduce a part 2 code block as the part 1 example shown again
+const REUSE_MARKERS: [&str; 4] = ["above", "before", "again", "same"];
+
+/// An example input from a problem description and the answers given for it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocExample {
+    pub input: String,
+    /// Line of the opening fence in the markdown
+    pub line: usize,
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+}
+
+/// Extracts the examples of a problem description. A part's example input is
+/// the first fenced block of its section, and its answer the last bolded number
+/// before "Your puzzle answer was". A part 2 block introduced as the example
+/// "above", "again" or "the same" is the part 1 input repeated, so its answer
+/// goes to the part 1 example instead.
+pub fn extract(markdown: &str) -> Vec<DocExample> {
+    let lines: Vec<(usize, &str)> = markdown.lines().enumerate().map(|(index, line)| (index + 1, line)).collect();
+    let split = lines
+        .iter()
+        .position(|(_, line)| line.starts_with(PART_TWO_HEADING))
+        .unwrap_or(lines.len());
+    let (part1, part2) = lines.split_at(split);
+
+    let Some((line, _, input)) = first_block(part1) else {
+        return Vec::new();
+    };
+    let mut examples = vec![DocExample { input, line, part1: bold_answer(part1), part2: None }];
+
+    let answer = bold_answer(part2);
+    match first_block(part2) {
+        Some((line, intro, input)) if !REUSE_MARKERS.iter().any(|word| intro.contains(word)) => {
+            examples.push(DocExample { input, line, part1: None, part2: answer });
+        }
+        _ => examples[0].part2 = answer,
+    }
+    examples
+}
+
+/// The first fenced block of a section: its line, the text before it and its contents
+fn first_block(section: &[(usize, &str)]) -> Option<(usize, String, String)> {
+    let open = section.iter().position(|(_, line)| line.trim_start().starts_with("```"))?;
+    let intro: Vec<&str> = section[..open].iter().map(|&(_, line)| line).collect();
+    let contents: Vec<&str> = section[open + 1..]
+        .iter()
+        .map(|&(_, line)| line)
+        .take_while(|line| !line.trim_start().starts_with("```"))
+        .collect();
+    Some((section[open].0, intro.join("\n"), contents.join("\n") + "\n"))
+}
+
+/// The last `**N**` of a section before its recorded answer
+fn bold_answer(section: &[(usize, &str)]) -> Option<Answer> {
+    let text: Vec<&str> = section
+        .iter()
+        .map(|&(_, line)| line)
+        .take_while(|line| !line.contains(ANSWER_PREFIX))
+        .collect();
+    // Splitting at `**` puts the bold text at every odd index
+    text.join("\n")
+        .split("**")
+        .skip(1)
+        .step_by(2)
+        .filter(|bold| {
+            let digits = bold.strip_prefix('-').unwrap_or(bold);
+            !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit())
+        })
+        .last()
+        .and_then(|bold| bold.parse().ok())
+}
+
+/// How a day's problem description compares with its manifest and input files
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExampleCheck {
+    pub day: u32,
+    pub problem_path: PathBuf,
+    /// The manifest's cases with the examples of the docs in place of the first
+    /// ones, answers from the docs first
+    pub cases: Vec<Case>,
+    /// Places where the docs and the manifest or input files disagree
+    pub mismatches: Vec<String>,
+    /// Differences that do not matter, such as line wrapping, and answers the
+    /// docs do not give, which are kept from the manifest instead
+    pub notes: Vec<String>,
+}
+
+/// Extracts the examples of a day's problem description and compares them, in
+/// order, with the first cases of its manifest and their input files. The
+/// manifest's `parts`, `params` and further cases, which the docs cannot give,
+/// are kept, so writing [`ExampleCheck::cases`] back loses nothing added by hand.
+pub fn check_day(solution: &dyn DynSolution, inputs: &Inputs) -> Result<ExampleCheck, String> {
+    let day = solution.day();
+    let problem_path = inputs.problem(day);
+    let markdown =
+        fs::read_to_string(&problem_path).map_err(|e| format!("cannot read {}: {}", problem_path.display(), e))?;
+    let docs = extract(&markdown);
+    let manifest_path = inputs.cases(day);
+    let manifest = manifest::read(&manifest_path).map_err(|e| e.to_string())?;
+    let mut check = ExampleCheck {
+        day,
+        problem_path: problem_path.clone(),
+        cases: Vec::new(),
+        mismatches: Vec::new(),
+        notes: Vec::new(),
+    };
+
+    if docs.is_empty() {
+        check.mismatches.push(format!("no example input found in {}", problem_path.display()));
+    }
+    if manifest.is_none() {
+        check.mismatches.push(format!("{} not found", manifest_path.display()));
+    }
+    let existing = manifest.as_deref().unwrap_or_default();
+
+    for (index, doc) in docs.iter().enumerate() {
+        let case = existing.get(index);
+        let name = match case {
+            Some(case) => case.name.clone(),
+            None => {
+                let name = if index == 0 { "test".to_string() } else { format!("test{}", index + 1) };
+                if manifest.is_some() {
+                    check.mismatches.push(format!(
+                        "{}: the example at {}:{} is not in {}",
+                        name,
+                        problem_path.display(),
+                        doc.line,
+                        manifest_path.display()
+                    ));
+                }
+                name
+            }
+        };
+
+        let source = match case.map(|case| &case.source) {
+            Some(Source::Inline(input)) if first_difference(&doc.input, input).is_none() => {
+                Source::Inline(input.clone())
+            }
+            Some(Source::Inline(input)) => {
+                let (line, docs_line, case_line) = first_difference(&doc.input, input).unwrap_or_default();
+                check.mismatches.push(format!(
+                    "{}: the input in {} differs from {}:{} at example line {}: \
+                     the docs have {:?}, the manifest has {:?}",
+                    name,
+                    manifest_path.display(),
+                    problem_path.display(),
+                    doc.line,
+                    line,
+                    docs_line,
+                    case_line
+                ));
+                Source::Inline(doc.input.clone())
+            }
+            Some(Source::File(file)) => compare_file(&mut check, &name, doc, inputs.root().join(file)),
+            None => compare_file(&mut check, &name, doc, inputs.named(day, &name)),
+        };
+
+        let mut answers = [None, None];
+        for part in Part::BOTH.into_iter().filter(|&part| solution.has_part(part)) {
+            let from_docs = match part {
+                Part::One => doc.part1.as_ref(),
+                Part::Two => doc.part2.as_ref(),
+            };
+            let from_manifest = case.and_then(|case| case.expected(part));
+            answers[part.number() as usize - 1] = match (from_docs, from_manifest) {
+                (Some(docs), Some(recorded)) if docs != recorded => {
+                    check.mismatches.push(format!(
+                        "{}: the part {} answer is {} in the docs but {} in the manifest",
+                        name,
+                        part.number(),
+                        docs,
+                        recorded
+                    ));
+                    Some(docs.clone())
+                }
+                (Some(docs), None) if case.is_some() => {
+                    check.mismatches.push(format!(
+                        "{}: the manifest does not check the part {} answer {} from the docs",
+                        name,
+                        part.number(),
+                        docs
+                    ));
+                    Some(docs.clone())
+                }
+                (None, Some(recorded)) => {
+                    check.notes.push(format!(
+                        "{}: no bolded part {} answer in the docs, keeping {} from the manifest",
+                        name,
+                        part.number(),
+                        recorded
+                    ));
+                    Some(recorded.clone())
+                }
+                (docs, _) => docs.cloned(),
+            };
+        }
+
+        let [part1, part2] = answers;
+        // The docs state a changed parameter in prose, so it only comes from the manifest
+        let (parts, params) = case.map(|case| (case.parts.clone(), case.params.clone())).unwrap_or_default();
+        check.cases.push(Case { name, source, parts, part1, part2, params });
+    }
+    // Cases past the docs' examples were added by hand
+    check.cases.extend(existing.iter().skip(docs.len()).cloned());
+
+    Ok(check)
+}
+
+/// The source of a case whose input file should match the docs: the file when
+/// it does, else the docs' input written into the manifest
+fn compare_file(check: &mut ExampleCheck, name: &str, doc: &DocExample, path: PathBuf) -> Source {
+    let problem_path = check.problem_path.display().to_string();
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+    match fs::read_to_string(&path) {
+        Ok(text) => match first_difference(&doc.input, &text) {
+            None => Source::File(file_name),
+            // e.g. a one-line input the puzzle text wraps to fit the page
+            Some(_) if without_whitespace(&doc.input) == without_whitespace(&text) => {
+                check.notes.push(format!(
+                    "{}: {} only differs from {}:{} in whitespace and line breaks",
+                    name,
+                    path.display(),
+                    problem_path,
+                    doc.line
+                ));
+                Source::File(file_name)
+            }
+            Some((line, docs_line, file_line)) => {
+                check.mismatches.push(format!(
+                    "{}: {} differs from {}:{} at example line {}: the docs have {:?}, the file has {:?}",
+                    name,
+                    path.display(),
+                    problem_path,
+                    doc.line,
+                    line,
+                    docs_line,
+                    file_line
+                ));
+                Source::Inline(doc.input.clone())
+            }
+        },
+        Err(_) => {
+            check.mismatches.push(format!("{}: {} not found", name, path.display()));
+            Source::Inline(doc.input.clone())
+        }
+    }
+}
+
+/// The first line where two inputs differ, ignoring line endings, trailing
+/// spaces and blank lines at either end, with the line from each
+fn first_difference(docs: &str, file: &str) -> Option<(usize, String, String)> {
+    let docs = significant_lines(docs);
+    let file = significant_lines(file);
+    (0..docs.len().max(file.len()))
+        .find(|&index| docs.get(index) != file.get(index))
+        .map(|index| {
+            let line = |lines: &[String]| lines.get(index).cloned().unwrap_or_else(|| "<end of input>".to_string());
+            (index + 1, line(&docs), line(&file))
+        })
+}
+
+fn without_whitespace(text: &str) -> String {
+    text.split_whitespace().collect()
+}
+
+fn significant_lines(text: &str) -> Vec<String> {
+    let input = Input::new(text);
+    let lines: Vec<&str> = input.as_str().lines().map(str::trim_end).collect();
+    let start = lines.iter().position(|line| !line.is_empty()).unwrap_or(lines.len());
+    let end = lines.iter().rposition(|line| !line.is_empty()).map_or(start, |end| end + 1);
+    lines[start..end].iter().map(|line| line.to_string()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn answer(value: i64) -> Option<Answer> {
+        Some(Answer::from(value))
+    }
+
+    const PLAIN: &str = "\
+## Part One
+For example:
+
+```
+1 2
+3 4
+```
+
+Adding the first column gives **4**, and the whole example adds up to **10**.
+
+Your puzzle answer was `1234`.
+
+## Part Two
+Now multiply instead; the example above now gives **24**.
+
+Your puzzle answer was `5678`.
+";
+
+    #[test]
+    fn takes_the_first_block_and_the_last_bold_number_of_a_part() {
+        let examples = extract(PLAIN);
+        assert_eq!(
+            examples,
+            [DocExample { input: "1 2\n3 4\n".to_string(), line: 4, part1: answer(10), part2: answer(24) }]
+        );
+    }
+
+    #[test]
+    fn ignores_bold_numbers_after_the_recorded_answer() {
+        let markdown = "```\nx\n```\n\nThat makes **7**.\n\nYour puzzle answer was **99**.\n\nOr **8**.\n";
+        assert_eq!(extract(markdown)[0].part1, answer(7));
+    }
+
+    #[test]
+    fn reads_only_whole_integers_as_answers() {
+        let markdown = "```\nx\n```\n\nThe **-3** is **not** a **1.5** nor **12abc**.\n";
+        assert_eq!(extract(markdown)[0].part1, answer(-3));
+    }
+
+    #[test]
+    fn gives_a_reused_part2_block_to_the_part1_example() {
+        for marker in REUSE_MARKERS {
+            let markdown = format!(
+                "```\na\n```\n\nGives **1**.\n\n## Part Two\nUsing the {} example:\n\n```\na\n```\n\nGives **2**.\n",
+                marker
+            );
+            let examples = extract(&markdown);
+            assert_eq!(examples.len(), 1, "marker {:?}", marker);
+            assert_eq!((&examples[0].part1, &examples[0].part2), (&answer(1), &answer(2)), "marker {:?}", marker);
+        }
+    }
+
+    #[test]
+    fn keeps_a_separate_part2_block_as_its_own_example() {
+        let markdown = "\
+Consider:
+
+```
+a
+```
+
+Gives **1**.
+
+## Part Two
+Here is a different example:
+
+```
+b
+c
+```
+
+Gives **2**.
+";
+        let examples = extract(markdown);
+        assert_eq!(
+            examples,
+            [
+                DocExample { input: "a\n".to_string(), line: 3, part1: answer(1), part2: None },
+                DocExample { input: "b\nc\n".to_string(), line: 12, part1: None, part2: answer(2) },
+            ]
+        );
+    }
+
+    #[test]
+    fn leaves_the_answer_out_when_a_section_bolds_no_number() {
+        let markdown = "```\na\n```\n\nThe **answer** is left to you.\n\n## Part Two\nNothing bold here.\n";
+        let examples = extract(markdown);
+        assert_eq!(examples.len(), 1);
+        assert_eq!((&examples[0].part1, &examples[0].part2), (&None, &None));
+    }
+
+    #[test]
+    fn finds_no_example_without_a_part1_block() {
+        assert!(extract("## Part One\nNo code here, only **5**.\n").is_empty());
+        assert!(extract("").is_empty());
+    }
+
+    #[test]
+    fn reads_an_unclosed_block_to_the_end_of_its_section() {
+        let markdown = "```\na\nb\n## Part Two\n```\nc\n```\n";
+        let examples = extract(markdown);
+        assert_eq!(examples[0].input, "a\nb\n");
+        assert_eq!(examples[1].input, "c\n");
+    }
+
+    #[test]
+    fn compares_inputs_line_by_line_ignoring_edges() {
+        assert_eq!(first_difference("\na \r\nb\n\n", "a\nb"), None);
+        assert_eq!(
+            first_difference("a\nb\n", "a\nc\nd\n"),
+            Some((2, "b".to_string(), "c".to_string()))
+        );
+        assert_eq!(
+            first_difference("a\n", "a\nd\n"),
+            Some((2, "<end of input>".to_string(), "d".to_string()))
+        );
+    }
+}
@@ -0,0 +1,149 @@
+use std::env;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Environment variable naming the inputs directory
+pub const INPUTS_ENV: &str = "AOC_INPUTS";
+/// Config file looked up in the current directory and its ancestors
+pub const CONFIG_FILE: &str = "aoc_rust.toml";
+/// Directory name searched for upward from the current directory
+const INPUTS_DIR: &str = "inputs";
+
+/// The directory holding every day's puzzle inputs.
+///
+/// Files are found by convention: `dayNN.txt` is the real input and
+/// `dayNN.cases.toml` lists the example cases to check, naming the input
+/// files next to it or holding the inputs inline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Inputs {
+    root: PathBuf,
+}
+
+/// Why the inputs directory could not be determined
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocateError(pub String);
+
+impl fmt::Display for LocateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for LocateError {}
+
+impl Inputs {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Inputs { root: root.into() }
+    }
+
+    /// Finds the inputs directory, in order of precedence: `flag` (from `--inputs`),
+    /// the `AOC_INPUTS` environment variable, the `inputs` key of the nearest
+    /// `aoc_rust.toml`, and finally the nearest `inputs/` directory at or above
+    /// the current directory.
+    pub fn locate(flag: Option<&Path>) -> Result<Inputs, LocateError> {
+        if let Some(path) = flag {
+            return Inputs::existing(path.to_path_buf(), "--inputs");
+        }
+        if let Some(path) = env::var_os(INPUTS_ENV).filter(|value| !value.is_empty()) {
+            return Inputs::existing(PathBuf::from(path), INPUTS_ENV);
+        }
+
+        let cwd = env::current_dir().map_err(|e| LocateError(format!("cannot read the current directory: {}", e)))?;
+        for dir in cwd.ancestors() {
+            let config = dir.join(CONFIG_FILE);
+            if let Ok(text) = fs::read_to_string(&config)
+                && let Some(path) = config_value(&text, "inputs")
+            {
+                // Relative paths in the config file are relative to the file itself
+                return Inputs::existing(dir.join(path), &config.display().to_string());
+            }
+        }
+
+        cwd.ancestors()
+            .map(|dir| dir.join(INPUTS_DIR))
+            .find(|path| path.is_dir())
+            .map(Inputs::new)
+            .ok_or_else(|| {
+                LocateError(format!(
+                    "no {}/ directory found above {}; pass --inputs DIR, set {} or add `inputs = \"DIR\"` to {}",
+                    INPUTS_DIR,
+                    cwd.display(),
+                    INPUTS_ENV,
+                    CONFIG_FILE
+                ))
+            })
+    }
+
+    fn existing(root: PathBuf, source: &str) -> Result<Inputs, LocateError> {
+        if root.is_dir() {
+            Ok(Inputs::new(root))
+        } else {
+            Err(LocateError(format!("inputs directory from {} not found: {}", source, root.display())))
+        }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// The real puzzle input, `dayNN.txt`
+    pub fn real(&self, day: u32) -> PathBuf {
+        self.root.join(format!("day{:02}.txt", day))
+    }
+
+    /// A named input such as `test` or `test_part2`, i.e. `dayNN_<name>.txt`
+    pub fn named(&self, day: u32, name: &str) -> PathBuf {
+        self.root.join(format!("day{:02}_{}.txt", day, name))
+    }
+
+    /// The day's example manifest, `dayNN.cases.toml`
+    pub fn cases(&self, day: u32) -> PathBuf {
+        self.root.join(format!("day{:02}.cases.toml", day))
+    }
+
+    /// Every input file of a day (`dayNN.txt` and `dayNN_*.txt`), sorted by file name
+    pub fn day_files(&self, day: u32) -> Vec<PathBuf> {
+        let day_prefix = format!("day{:02}", day);
+        self.starting_with(&day_prefix)
+            .into_iter()
+            .filter(|path| {
+                path.file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .is_some_and(|stem| stem == day_prefix || stem.starts_with(&format!("{}_", day_prefix)))
+            })
+            .collect()
+    }
+
+    /// The `.txt` files whose name starts with `prefix`, sorted
+    fn starting_with(&self, prefix: &str) -> Vec<PathBuf> {
+        let mut paths: Vec<PathBuf> = fs::read_dir(&self.root)
+            .into_iter()
+            .flatten()
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.starts_with(prefix) && name.ends_with(".txt"))
+            })
+            .collect();
+        paths.sort();
+        paths
+    }
+
+    /// The problem descriptions kept next to the inputs, `docs/problems/dayNN.md`
+    pub fn problem(&self, day: u32) -> PathBuf {
+        let repo = self.root.parent().unwrap_or(Path::new(".."));
+        repo.join("docs").join("problems").join(format!("day{:02}.md", day))
+    }
+}
+
+/// Reads `key = "value"` from a flat TOML-style config, ignoring comments and sections
+fn config_value(text: &str, key: &str) -> Option<String> {
+    text.lines()
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .filter_map(|line| line.split_once('='))
+        .find(|(name, _)| name.trim() == key)
+        .map(|(_, value)| value.trim().trim_matches('"').to_string())
+}
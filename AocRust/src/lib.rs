@@ -0,0 +1,39 @@
+//! Advent of Code 2025 solutions in Rust.
+//!
+//! Every day lives in its own `dayNN` module and exposes its parsers and
+//! solvers as plain functions, plus a unit struct implementing
+//! [`solution::Solution`]. The [`registry`] lists all implemented days and the
+//! [`runner`] drives them; the `aoc_rust` binary is a thin front end over these.
+
+#![allow(clippy::needless_range_loop, clippy::too_many_arguments)]
+
+pub mod answer;
+pub mod bench;
+pub mod budget;
+pub mod error;
+pub mod examples;
+pub mod inputs;
+pub mod manifest;
+pub mod memory;
+pub mod parallel;
+pub mod params;
+pub mod registry;
+pub mod report;
+pub mod runner;
+pub mod scaffold;
+pub mod solution;
+pub mod utils;
+pub mod verify;
+pub mod watch;
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
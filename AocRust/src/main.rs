@@ -1,91 +1,487 @@
+use std::any::Any;
 use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
 
-mod utils;
-mod day01;
-mod day02;
-mod day03;
-mod day04;
-mod day05;
-mod day06;
-mod day07;
-mod day08;
-mod day09;
-mod day10;
-mod day11;
-mod day12;
-
-fn main() {
+mod cli;
+
+use aoc_rust::bench::{self, BenchConfig};
+use aoc_rust::budget::{self, Budget};
+use aoc_rust::inputs::Inputs;
+use aoc_rust::memory::{self, AllocStats};
+use aoc_rust::report::{Format, InputKind, Outcome, Record};
+use aoc_rust::solution::{DynSolution, Part};
+use aoc_rust::{examples, manifest, scaffold, verify, watch};
+use aoc_rust::{parallel, registry, runner, utils};
+use cli::{Command, Days};
+
+fn main() -> ExitCode {
+    let args = match cli::parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, cli::USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    parallel::set_jobs(args.jobs);
+    budget::set_limits(args.limits);
+
+    // The inputs directory is not needed when every part that runs has an explicit input
+    let needs_inputs = match &args.command {
+        Command::Help => false,
+        Command::Run { days: Days::One(_), part, input, part_inputs, .. } => {
+            Part::BOTH
+                .into_iter()
+                .filter(|p| part.is_none_or(|only| only == *p))
+                .any(|p| input.is_none() && part_inputs[p.number() as usize - 1].is_none())
+        }
+        _ => true,
+    };
+    let inputs = match Inputs::locate(args.inputs.as_deref()) {
+        Ok(inputs) => inputs,
+        Err(e) if needs_inputs => {
+            eprintln!("error: {}", e);
+            return ExitCode::FAILURE;
+        }
+        Err(_) => Inputs::new(Path::new("inputs")),
+    };
+
+    match args.command {
+        Command::Interactive => {
+            interactive(&inputs);
+            ExitCode::SUCCESS
+        }
+        Command::Help => {
+            println!("{}", cli::USAGE);
+            ExitCode::SUCCESS
+        }
+        Command::Run { days, part, input, part_inputs, format, params } => {
+            exit_code(solve_days(days, part, input.as_deref(), &part_inputs, format, &params, &inputs))
+        }
+        Command::Test { days, format } => exit_code(test_days(days, format, &inputs)),
+        Command::Verify { days, format } => exit_code(verify_days(days, format, &inputs)),
+        Command::Examples { days, write } => exit_code(examples_days(days, write, &inputs)),
+        Command::Bench { days, part, config, params } => exit_code(bench_days(days, part, &config, &params, &inputs)),
+        Command::Watch { day, interval } => match registry::get(day) {
+            Some(solution) => {
+                watch::watch(solution, &inputs, interval);
+                ExitCode::SUCCESS
+            }
+            None => {
+                eprintln!("Day {} not implemented yet!", day);
+                ExitCode::FAILURE
+            }
+        },
+        Command::NewDay { day, title } => match scaffold::new_day(day, title.as_deref(), &inputs) {
+            Ok(changes) => {
+                for change in changes {
+                    println!("{}", change);
+                }
+                println!("\nRebuild and run `aoc_rust test --day {}` to try it", day);
+                ExitCode::SUCCESS
+            }
+            Err(e) => {
+                eprintln!("error: {}", e);
+                ExitCode::FAILURE
+            }
+        },
+    }
+}
+
+fn exit_code(success: bool) -> ExitCode {
+    if success { ExitCode::SUCCESS } else { ExitCode::FAILURE }
+}
+
+fn interactive(inputs: &Inputs) {
     println!("\n{}", "=".repeat(50));
     println!("🎄 Advent of Code 2025 - Rust Solutions 🎄");
     println!("{}\n", "=".repeat(50));
 
     loop {
-        print!("Select a day (1-25) or 0 to exit: ");
+        print!("Select a day (1-25), 'all' to run every day, or 0 to exit: ");
         io::stdout().flush().unwrap();
 
         let mut input = String::new();
-        io::stdin().read_line(&mut input).unwrap();
+        // End of input (e.g. piped stdin running out) exits like 0
+        if io::stdin().read_line(&mut input).unwrap() == 0 {
+            println!();
+            break;
+        }
+
+        if input.trim().eq_ignore_ascii_case("all") {
+            runner::run_all(inputs);
+            continue;
+        }
 
         match input.trim().parse::<u32>() {
             Ok(0) => {
                 println!("\n🎄 Happy Coding! 🎄\n");
                 break;
             }
-            Ok(1) => {
-                day01::run();
-                println!(); // Add blank line after running a day
+            Ok(day) => match registry::get(day) {
+                Some(solution) => {
+                    runner::run_day(solution, inputs, true);
+                    println!(); // Add blank line after running a day
+                }
+                None => println!("\n⚠ Day {} not implemented yet!\n", day),
+            },
+            Err(_) => {
+                println!("Invalid input. Please enter a number.\n");
             }
-            // Add more days here as you implement them
-            Ok(2) => {
-                day02::run();
-                println!(); // Add blank line after running a day
+        }
+    }
+}
+
+fn selected_days(days: Days) -> Vec<u32> {
+    match days {
+        Days::One(day) => vec![day],
+        Days::All => registry::all().iter().map(|solution| solution.day()).collect(),
+    }
+}
+
+/// Prints the answers for the selected days, returning false on any error.
+/// Each part reads its own input if one was given, else the shared input, else
+/// the day's real input; an input used by both parts is read and parsed once.
+/// With `--jobs` the days are solved concurrently and printed in day order.
+fn solve_days(
+    days: Days,
+    part: Option<Part>,
+    input_path: Option<&str>,
+    part_inputs: &[Option<String>; 2],
+    format: Option<Format>,
+    params: &[(String, String)],
+    inputs: &Inputs,
+) -> bool {
+    let mut success = true;
+    let mut solutions = Vec::new();
+    for day in selected_days(days) {
+        match registry::get(day) {
+            Some(solution) => solutions.push(solution),
+            None => {
+                eprintln!("Day {} not implemented yet!", day);
+                success = false;
             }
-            Ok(3) => {
-                day03::run();
-                println!(); // Add blank line after running a day
+        }
+    }
+
+    // A single requested part prints the bare answer so it can be piped
+    let bare = part.is_some() && days != Days::All;
+    let solved = parallel::map(&solutions, parallel::jobs(), |solution| {
+        solve_day(*solution, part, bare, input_path, part_inputs, params, inputs)
+    });
+
+    let mut records = Vec::new();
+    for Solved { records: day_records, errors } in solved {
+        for record in &day_records {
+            match &record.outcome.answer {
+                _ if format.is_some() => {}
+                Some(answer) if bare => println!("{}", answer),
+                Some(answer) => println!("Day {:02} {}: {}", record.day, record.part, answer),
+                None => {}
             }
-            Ok(4) => {
-                day04::run();
-                println!(); // Add blank line after running a day
+        }
+        for error in &errors {
+            eprintln!("{}", error);
+        }
+        success &= errors.is_empty() && !day_records.iter().any(|record| record.outcome.status.is_failure());
+        records.extend(day_records);
+    }
+
+    if let Some(format) = format {
+        print!("{}", format.render(&records));
+    }
+    success
+}
+
+/// What solving one day produced: a record per part, and the errors to report
+struct Solved {
+    records: Vec<Record>,
+    errors: Vec<String>,
+}
+
+/// Solves the requested parts of one day without printing anything
+fn solve_day(
+    solution: &dyn DynSolution,
+    part: Option<Part>,
+    bare: bool,
+    input_path: Option<&str>,
+    part_inputs: &[Option<String>; 2],
+    params: &[(String, String)],
+    inputs: &Inputs,
+) -> Solved {
+    let day = solution.day();
+    let parts = match part {
+        Some(part) => vec![part],
+        None => Part::BOTH.to_vec(),
+    };
+    let mut solved = Solved { records: Vec::new(), errors: Vec::new() };
+    let params = match solution.params(params) {
+        Ok(params) => params,
+        Err(e) => {
+            solved.errors.push(format!("Day {}: {}", day, e));
+            return solved;
+        }
+    };
+    let mut parsed_inputs: Vec<(PathBuf, Parsed)> = Vec::new();
+
+    for part in parts {
+        if !solution.has_part(part) {
+            if bare {
+                solved.errors.push(format!("Day {} has no {}", day, part.to_string().to_lowercase()));
             }
-            Ok(5) => {
-                day05::run();
-                println!(); // Add blank line after running a day
+            continue;
+        }
+
+        let (path, kind) = match part_inputs[part.number() as usize - 1].as_deref().or(input_path) {
+            Some(path) => (PathBuf::from(path), InputKind::Custom),
+            None => (inputs.real(day), InputKind::Real),
+        };
+        let index = match parsed_inputs.iter().position(|(parsed_path, _)| *parsed_path == path) {
+            Some(index) => index,
+            None => {
+                let parsed = load(solution, day, &path);
+                // Input errors are reported once, however many parts use the input
+                if let Err(e) = &parsed {
+                    solved.errors.push(e.clone());
+                }
+                parsed_inputs.push((path.clone(), parsed));
+                parsed_inputs.len() - 1
             }
-            Ok(6) => {
-                day06::run();
-                println!(); // Add blank line after running a day
+        };
+        let outcome = match &parsed_inputs[index].1 {
+            Ok(parsed) => utils::check(
+                || solution.solve(part, parsed.as_ref(), params.as_ref(), &solution.budget(params.as_ref())),
+                None,
+            ),
+            Err(e) => Outcome::error(e, None),
+        };
+
+        if let (Ok(_), Some(e)) = (&parsed_inputs[index].1, &outcome.error) {
+            solved.errors.push(format!("Day {} {}: {}", day, part, e));
+        }
+        solved.records.push(Record { day, part, input: kind, path: display_path(&path), outcome });
+    }
+
+    solved
+}
+
+fn display_path(path: &Path) -> String {
+    if path.as_os_str() == utils::STDIN_PATH {
+        "<stdin>".to_string()
+    } else {
+        path.display().to_string()
+    }
+}
+
+/// A parsed input, or why it could not be read or parsed
+type Parsed = Result<Box<dyn Any>, String>;
+
+/// Reads and parses a day's input, describing any failure
+fn load(solution: &dyn DynSolution, day: u32, path: &Path) -> Parsed {
+    let input = utils::read_input(path).map_err(|e| format!("Day {}: {}", day, e))?;
+    solution
+        .parse(&input)
+        .map_err(|e| format!("{}: {}", display_path(path), e))
+}
+
+/// Runs the example checks and real inputs, returning false if any of them fails
+fn test_days(days: Days, format: Option<Format>, inputs: &Inputs) -> bool {
+    // Every day in text mode is the same run as the interactive "all" choice
+    if days == Days::All && format.is_none() {
+        let records = runner::run_all(inputs);
+        return !records.iter().any(|record| record.outcome.status.is_failure());
+    }
+
+    let Some(solutions) = selected_solutions(days) else {
+        return false;
+    };
+    let records = match format {
+        Some(format) => {
+            let records = runner::run_days(&solutions, inputs, parallel::jobs());
+            print!("{}", format.render(&records));
+            records
+        }
+        None => {
+            let mut records = Vec::new();
+            for solution in solutions {
+                println!("\n--- Day {}: {} ---", solution.day(), solution.title());
+                records.extend(runner::run_day(solution, inputs, true));
             }
-            Ok(7) => {
-                day07::run();
-                println!(); // Add blank line after running a day
+            records
+        }
+    };
+    !records.iter().any(|record| record.outcome.status.is_failure())
+}
+
+/// Checks real-input answers against the recorded ones, returning false on any mismatch
+fn verify_days(days: Days, format: Option<Format>, inputs: &Inputs) -> bool {
+    let Some(solutions) = selected_solutions(days) else {
+        return false;
+    };
+    let mut counts = [0; 6];
+    let mut records = Vec::new();
+
+    let verified = parallel::map(&solutions, parallel::jobs(), |solution| verify::verify_day(*solution, inputs));
+    for verification in verified.into_iter().flatten() {
+        if format.is_none() {
+            println!("{}", verification);
+        }
+        counts[verification.status as usize] += 1;
+        records.push(verification.to_record());
+    }
+
+    let [passed, failed, errors, missing, _, timed_out] = counts;
+    match format {
+        Some(format) => print!("{}", format.render(&records)),
+        None => println!(
+            "\n{} passed, {} failed, {} errors, {} timed out, {} missing",
+            passed, failed, errors, timed_out, missing
+        ),
+    }
+    failed == 0 && errors == 0 && timed_out == 0
+}
+
+/// Compares the examples of the problem descriptions with the manifests and
+/// their input files, writing the manifests with `write`; false on any mismatch
+fn examples_days(days: Days, write: bool, inputs: &Inputs) -> bool {
+    let Some(solutions) = selected_solutions(days) else {
+        return false;
+    };
+    let mut success = true;
+
+    for solution in solutions {
+        println!("\n--- Day {}: {} ---", solution.day(), solution.title());
+        let check = match examples::check_day(solution, inputs) {
+            Ok(check) => check,
+            Err(e) => {
+                println!("✗ {}", e);
+                success = false;
+                continue;
             }
-            Ok(8) => {
-                day08::run();
-                println!(); // Add blank line after running a day
+        };
+
+        for case in &check.cases {
+            let source = match &case.source {
+                manifest::Source::File(file) => file.clone(),
+                manifest::Source::Inline(_) => "inline input".to_string(),
+            };
+            let answers: Vec<String> = [(1, &case.part1), (2, &case.part2)]
+                .into_iter()
+                .filter_map(|(part, answer)| Some(format!("part {} = {}", part, answer.as_ref()?)))
+                .collect();
+            println!("  {}: {}, {}", case.name, source, answers.join(", "));
+        }
+        for note in &check.notes {
+            println!("⚠ {}", note);
+        }
+        for mismatch in &check.mismatches {
+            println!("✗ {}", mismatch);
+        }
+        success &= check.mismatches.is_empty();
+
+        if write && !check.cases.is_empty() {
+            let path = inputs.cases(check.day);
+            let toml = manifest::to_toml(check.day, solution.title(), &check.cases);
+            match std::fs::write(&path, toml) {
+                Ok(()) => println!("Wrote {}", path.display()),
+                Err(e) => {
+                    eprintln!("error: cannot write {}: {}", path.display(), e);
+                    success = false;
+                }
             }
-            Ok(9) => {
-                day09::run();
-                println!(); // Add blank line after running a day
+        }
+    }
+
+    success
+}
+
+/// The registered solutions for the selected days, or `None` after reporting a missing day
+fn selected_solutions(days: Days) -> Option<Vec<&'static dyn DynSolution>> {
+    let mut solutions = Vec::new();
+    for day in selected_days(days) {
+        match registry::get(day) {
+            Some(solution) => solutions.push(solution),
+            None => {
+                eprintln!("Day {} not implemented yet!", day);
+                return None;
             }
-            Ok(10) => {
-                day10::run();
-                println!(); // Add blank line after running a day
+        }
+    }
+    Some(solutions)
+}
+
+/// Benchmarks every selected part on its real input, returning false if an input
+/// or a parameter is missing or invalid
+fn bench_days(
+    days: Days,
+    part: Option<Part>,
+    config: &BenchConfig,
+    params: &[(String, String)],
+    inputs: &Inputs,
+) -> bool {
+    let mut success = true;
+    let allocations_header = if memory::enabled() { "  Allocations (one run)" } else { "" };
+    println!("Day  Part    {}{}", bench::STATS_HEADER, allocations_header);
+
+    for day in selected_days(days) {
+        let Some(solution) = registry::get(day) else {
+            eprintln!("Day {} not implemented yet!", day);
+            success = false;
+            continue;
+        };
+        let params = match solution.params(params) {
+            Ok(params) => params,
+            Err(e) => {
+                eprintln!("Day {}: {}", day, e);
+                success = false;
+                continue;
             }
-            Ok(11) => {
-                day11::run();
-                println!(); // Add blank line after running a day
+        };
+        let path = inputs.real(day);
+        let input = match utils::read_input(&path) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("Day {}: {}", day, e);
+                success = false;
+                continue;
             }
-            Ok(12) => {
-                day12::run();
-                println!(); // Add blank line after running a day
+        };
+
+        let parsed = match solution.parse(&input) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                eprintln!("{}: {}", path.display(), e);
+                success = false;
+                continue;
             }
-            Ok(day) => {
-                println!("\n⚠ Day {} not implemented yet!\n", day);
+        };
+        // Parsing is timed on its own so the part rows only measure solving
+        let stats = bench::bench(config, || solution.parse(&input));
+        let (_, memory) = memory::measure(|| solution.parse(&input));
+        println!("{:02}   {:<6}  {}{}", day, "Parse", stats, allocations_column(memory));
+
+        for current in Part::BOTH {
+            if part.is_some_and(|p| p != current) || !solution.has_part(current) {
+                continue;
             }
-            Err(_) => {
-                println!("Invalid input. Please enter a number.\n");
+            // One run within the limits first, so a part that times out is not benchmarked
+            if let Err(e) = solution.solve(current, parsed.as_ref(), params.as_ref(), &solution.budget(params.as_ref())) {
+                eprintln!("Day {} {}: {}", day, current, e);
+                success = false;
+                continue;
             }
+            let solve = || solution.solve(current, parsed.as_ref(), params.as_ref(), &Budget::unlimited());
+            let stats = bench::bench(config, solve);
+            let (_, memory) = memory::measure(solve);
+            println!("{:02}   {}  {}{}", day, current, stats, allocations_column(memory));
         }
     }
+
+    success
+}
+
+/// The allocations of one run after the bench statistics, with the `alloc-stats` feature
+fn allocations_column(memory: Option<AllocStats>) -> String {
+    memory.map(|memory| format!("  {}", memory)).unwrap_or_default()
 }
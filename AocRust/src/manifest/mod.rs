@@ -0,0 +1,548 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::answer::Answer;
+use crate::inputs::Inputs;
+use crate::solution::Part;
+
+/// Why a manifest could not be read
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestError(pub String);
+
+impl fmt::Display for ManifestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for ManifestError {}
+
+/// Where a case's input comes from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    /// A file in the inputs directory, e.g. `day11_test.txt`
+    File(String),
+    /// The input itself, written into the manifest
+    Inline(String),
+}
+
+/// One example in a day's manifest: an input and the answers it should produce
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Case {
+    pub name: String,
+    pub source: Source,
+    /// Parts the case is run for; `None` runs every part it has an answer for,
+    /// so a case whose answers are not filled in yet is skipped
+    pub parts: Option<Vec<Part>>,
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+    /// Settings the puzzle text changes for the example, by name, from `[case.params]`
+    pub params: Vec<(String, String)>,
+}
+
+impl Case {
+    pub fn expected(&self, part: Part) -> Option<&Answer> {
+        match part {
+            Part::One => self.part1.as_ref(),
+            Part::Two => self.part2.as_ref(),
+        }
+    }
+
+    /// The parts to run, in order
+    pub fn parts(&self) -> Vec<Part> {
+        match &self.parts {
+            Some(parts) => parts.clone(),
+            None => Part::BOTH.into_iter().filter(|&part| self.expected(part).is_some()).collect(),
+        }
+    }
+}
+
+/// A day's example cases, from its manifest `inputs/dayNN.cases.toml`
+pub fn day_cases(day: u32, inputs: &Inputs) -> Result<Vec<Case>, ManifestError> {
+    let path = inputs.cases(day);
+    read(&path)?.ok_or_else(|| {
+        ManifestError(format!(
+            "{} not found; `aoc_rust examples --day {} --write` creates it from the problem description",
+            path.display(),
+            day
+        ))
+    })
+}
+
+/// Reads the manifest at `path`, `None` when there is none yet
+pub fn read(path: &Path) -> Result<Option<Vec<Case>>, ManifestError> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(ManifestError(format!("cannot read {}: {}", path.display(), e))),
+    };
+    parse(&text).map(Some).map_err(|e| ManifestError(format!("{}: {}", path.display(), e)))
+}
+
+/// Reads a manifest: a subset of TOML with one `[[case]]` table per case, holding
+/// `name`, either `file` or `input`, optionally `parts = [1, 2]`, the `part1` and
+/// `part2` answers, and a `[case.params]` table of `key = value` settings
+pub fn parse(text: &str) -> Result<Vec<Case>, ManifestError> {
+    let mut cases: Vec<Partial> = Vec::new();
+    let mut in_params = false;
+    let mut lines = text.lines().enumerate().map(|(index, line)| (index + 1, line));
+
+    while let Some((number, line)) = lines.next() {
+        let error = |msg: String| ManifestError(format!("line {}: {}", number, msg));
+        let content = strip_comment(line).trim();
+        if content.is_empty() {
+            continue;
+        }
+        match content {
+            "[[case]]" => {
+                cases.push(Partial { line: number, ..Partial::default() });
+                in_params = false;
+                continue;
+            }
+            "[case.params]" if !cases.is_empty() => {
+                in_params = true;
+                continue;
+            }
+            _ if content.starts_with('[') => return Err(error(format!("unexpected table {}", content))),
+            _ => {}
+        }
+
+        let Some(case) = cases.last_mut() else {
+            return Err(error("settings before the first [[case]]".to_string()));
+        };
+        let (key, value) = content
+            .split_once('=')
+            .ok_or_else(|| error(format!("expected `key = value`, found '{}'", content)))?;
+        let key = key.trim();
+        let value = read_value(value.trim_start(), &mut lines).map_err(error)?;
+
+        if in_params {
+            if case.params.iter().any(|(name, _)| name == key) {
+                return Err(error(format!("parameter {} is set twice", key)));
+            }
+            case.params.push((key.to_string(), value.into_text()));
+            continue;
+        }
+        let slot = match key {
+            "name" => &mut case.name,
+            "file" => &mut case.file,
+            "input" => &mut case.input,
+            "parts" => &mut case.parts,
+            "part1" => &mut case.part1,
+            "part2" => &mut case.part2,
+            _ => return Err(error(format!("unknown key {}", key))),
+        };
+        if slot.replace(value).is_some() {
+            return Err(error(format!("{} is set twice", key)));
+        }
+    }
+
+    let cases: Vec<Case> = cases.into_iter().map(Partial::finish).collect::<Result<_, _>>()?;
+    for (index, case) in cases.iter().enumerate() {
+        if cases[..index].iter().any(|other| other.name == case.name) {
+            return Err(ManifestError(format!("two cases are named {}", case.name)));
+        }
+    }
+    Ok(cases)
+}
+
+/// A `[[case]]` table as it is read, before its keys are checked
+#[derive(Debug, Default)]
+struct Partial {
+    line: usize,
+    name: Option<Value>,
+    file: Option<Value>,
+    input: Option<Value>,
+    parts: Option<Value>,
+    part1: Option<Value>,
+    part2: Option<Value>,
+    params: Vec<(String, String)>,
+}
+
+impl Partial {
+    fn finish(self) -> Result<Case, ManifestError> {
+        let error = |msg: String| ManifestError(format!("case starting at line {}: {}", self.line, msg));
+        let name = self.name.ok_or_else(|| error("missing name".to_string()))?.into_text();
+        let source = match (self.file, self.input) {
+            (Some(file), None) => Source::File(file.into_text()),
+            (None, Some(input)) => Source::Inline(input.into_text()),
+            _ => return Err(error(format!("{} needs either a file or an input", name))),
+        };
+        let answer = |value: Option<Value>| value.and_then(|value| value.into_text().parse::<Answer>().ok());
+        let parts = match self.parts {
+            Some(Value::Integers(numbers)) => Some(
+                numbers
+                    .iter()
+                    .map(|&number| {
+                        u8::try_from(number)
+                            .ok()
+                            .and_then(Part::from_number)
+                            .ok_or_else(|| error(format!("{}: invalid part {}, expected 1 or 2", name, number)))
+                    })
+                    .collect::<Result<Vec<Part>, _>>()?,
+            ),
+            Some(_) => return Err(error(format!("{}: parts must be a list such as [1, 2]", name))),
+            None => None,
+        };
+
+        let case = Case {
+            source,
+            parts,
+            part1: answer(self.part1),
+            part2: answer(self.part2),
+            params: self.params,
+            name,
+        };
+        match case.parts().into_iter().find(|&part| case.expected(part).is_none()) {
+            Some(part) => {
+                Err(error(format!("{} runs part {} but has no part{} answer", case.name, part.number(), part.number())))
+            }
+            None => Ok(case),
+        }
+    }
+}
+
+/// A value on the right of `=`
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Value {
+    Text(String),
+    Integer(i64),
+    Integers(Vec<i64>),
+}
+
+impl Value {
+    /// The value as text, the way answers and parameters are kept
+    fn into_text(self) -> String {
+        match self {
+            Value::Text(text) => text,
+            Value::Integer(n) => n.to_string(),
+            Value::Integers(numbers) => numbers.iter().map(i64::to_string).collect::<Vec<_>>().join(","),
+        }
+    }
+}
+
+/// Reads a string, integer or integer list; a `'''` block continues over the following lines
+fn read_value<'a>(value: &str, lines: &mut impl Iterator<Item = (usize, &'a str)>) -> Result<Value, String> {
+    if let Some(rest) = value.strip_prefix("'''") {
+        // As in TOML, a line break right after the opening quotes is not part of the text
+        let mut text = String::new();
+        let mut current = rest.to_string();
+        let mut first = true;
+        loop {
+            if let Some(end) = current.find("'''") {
+                text.push_str(&current[..end]);
+                return match strip_comment(&current[end + 3..]).trim() {
+                    "" => Ok(Value::Text(text)),
+                    extra => Err(format!("unexpected '{}' after the text", extra)),
+                };
+            }
+            if !(first && current.is_empty()) {
+                text.push_str(&current);
+                text.push('\n');
+            }
+            first = false;
+            current = lines.next().ok_or("the ''' text is never closed")?.1.to_string();
+        }
+    }
+
+    let value = strip_comment(value).trim();
+    if value.starts_with("\"\"\"") {
+        return Err("multi-line text must use ''' quotes".to_string());
+    }
+    if let Some(rest) = value.strip_prefix('"') {
+        let (text, extra) = read_basic_string(rest)?;
+        return match extra.trim() {
+            "" => Ok(Value::Text(text)),
+            extra => Err(format!("unexpected '{}' after the text", extra)),
+        };
+    }
+    if let Some(text) = value.strip_prefix('\'').and_then(|rest| rest.strip_suffix('\'')) {
+        return Ok(Value::Text(text.to_string()));
+    }
+    if let Some(list) = value.strip_prefix('[').and_then(|rest| rest.strip_suffix(']')) {
+        return list
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(|item| item.parse().map_err(|_| format!("expected a number in the list, found '{}'", item)))
+            .collect::<Result<_, _>>()
+            .map(Value::Integers);
+    }
+    value
+        .replace('_', "")
+        .parse()
+        .map(Value::Integer)
+        .map_err(|_| format!("expected a string, number or list, found '{}'", value))
+}
+
+/// A `"..."` string after its opening quote, with escapes resolved, and whatever follows it
+fn read_basic_string(rest: &str) -> Result<(String, &str), String> {
+    let mut text = String::new();
+    let mut chars = rest.char_indices();
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '"' => return Ok((text, &rest[index + 1..])),
+            '\\' => match chars.next().map(|(_, c)| c) {
+                Some('n') => text.push('\n'),
+                Some('r') => text.push('\r'),
+                Some('t') => text.push('\t'),
+                Some('"') => text.push('"'),
+                Some('\\') => text.push('\\'),
+                Some('u') => {
+                    let hex: String = chars.by_ref().take(4).map(|(_, c)| c).collect();
+                    let c = u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32);
+                    text.push(c.ok_or_else(|| format!("invalid escape \\u{}", hex))?);
+                }
+                other => return Err(format!("invalid escape \\{}", other.map(String::from).unwrap_or_default())),
+            },
+            c => text.push(c),
+        }
+    }
+    Err("the string is never closed".to_string())
+}
+
+/// The line up to a `#` that is outside any quotes
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    let mut escaped = false;
+    for (index, c) in line.char_indices() {
+        match (quote, c) {
+            (Some('"'), '\\') if !escaped => {
+                escaped = true;
+                continue;
+            }
+            (Some(open), c) if c == open && !escaped => quote = None,
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '#') => return &line[..index],
+            _ => {}
+        }
+        escaped = false;
+    }
+    line
+}
+
+/// The manifest `inputs/dayNN.cases.toml` for `cases`, one `[[case]]` table each
+pub fn to_toml(day: u32, title: &str, cases: &[Case]) -> String {
+    let mut toml = format!("# Day {:02}: {}\n", day, title);
+    for case in cases {
+        toml.push_str(&format!("\n[[case]]\nname = {}\n", toml_string(&case.name)));
+        match &case.source {
+            Source::File(file) => toml.push_str(&format!("file = {}\n", toml_string(file))),
+            // A literal block keeps the input readable; it cannot hold `'''` itself
+            Source::Inline(input) if !input.contains("'''") => {
+                toml.push_str(&format!("input = '''\n{}\n'''\n", input.trim_end_matches('\n')))
+            }
+            Source::Inline(input) => toml.push_str(&format!("input = {}\n", toml_string(input))),
+        }
+        if let Some(parts) = &case.parts {
+            let numbers: Vec<String> = parts.iter().map(|part| part.number().to_string()).collect();
+            toml.push_str(&format!("parts = [{}]\n", numbers.join(", ")));
+        }
+        for (key, answer) in [("part1", &case.part1), ("part2", &case.part2)] {
+            if let Some(answer) = answer {
+                toml.push_str(&format!("{} = {}\n", key, toml_value(&answer.to_string())));
+            }
+        }
+        if !case.params.is_empty() {
+            toml.push_str("\n[case.params]\n");
+            for (key, value) in &case.params {
+                toml.push_str(&format!("{} = {}\n", key, toml_value(value)));
+            }
+        }
+    }
+    toml
+}
+
+/// A TOML integer when the text is one that fits, else a string
+fn toml_value(text: &str) -> String {
+    if text.parse::<i64>().is_ok() { text.to_string() } else { toml_string(text) }
+}
+
+fn toml_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn case(name: &str, source: Source) -> Case {
+        Case { name: name.to_string(), source, parts: None, part1: None, part2: None, params: Vec::new() }
+    }
+
+    fn parse_one(text: &str) -> Case {
+        let mut cases = parse(text).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(cases.len(), 1);
+        cases.remove(0)
+    }
+
+    fn assert_errors(cases: &[(&str, &str)]) {
+        for &(text, expected) in cases {
+            match parse(text) {
+                Ok(cases) => panic!("expected an error for {:?}, parsed {:?}", text, cases),
+                Err(ManifestError(message)) => assert_eq!(message, expected, "for {:?}", text),
+            }
+        }
+    }
+
+    #[test]
+    fn reads_a_file_case_with_answers() {
+        let parsed = parse_one("[[case]]\nname = \"test\"\nfile = \"day01_test.txt\"\npart1 = 3\npart2 = -6\n");
+        let expected = Case {
+            part1: Some(Answer::I64(3)),
+            part2: Some(Answer::I64(-6)),
+            ..case("test", Source::File("day01_test.txt".to_string()))
+        };
+        assert_eq!(parsed, expected);
+        assert_eq!(parsed.parts(), vec![Part::One, Part::Two]);
+    }
+
+    #[test]
+    fn resolves_escapes_in_basic_strings() {
+        let parsed = parse_one("[[case]]\nname = \"a\\\"b\\\\c\\td\\u00e9\"\ninput = \"1\\n2\\r\\n\"\npart1 = 1\n");
+        assert_eq!(parsed.name, "a\"b\\c\td\u{e9}");
+        assert_eq!(parsed.source, Source::Inline("1\n2\r\n".to_string()));
+    }
+
+    #[test]
+    fn keeps_literal_strings_as_written() {
+        let parsed = parse_one("[[case]]\nname = 'C:\\dir\\n'\nfile = 'x.txt'\npart1 = 1\n");
+        assert_eq!(parsed.name, "C:\\dir\\n");
+    }
+
+    #[test]
+    fn reads_multi_line_literal_blocks() {
+        let text = "[[case]]\nname = \"block\"\ninput = '''\n  a # b\n\nc\\n\n''' # done\npart1 = 1\n";
+        assert_eq!(parse_one(text).source, Source::Inline("  a # b\n\nc\\n\n".to_string()));
+
+        let same_line = parse_one("[[case]]\nname = \"x\"\ninput = '''one'''\npart1 = 1\n");
+        assert_eq!(same_line.source, Source::Inline("one".to_string()));
+    }
+
+    #[test]
+    fn reads_integers_lists_and_large_answers() {
+        let text = "[[case]]\nname = \"n\"\nfile = \"f\"\nparts = [2, ]\npart1 = 1_000\n\
+                    part2 = \"340282366920938463463374607431768211455\"\n";
+        let parsed = parse_one(text);
+        assert_eq!(parsed.parts, Some(vec![Part::Two]));
+        assert_eq!(parsed.part1, Some(Answer::I64(1000)));
+        assert_eq!(parsed.part2, Some(Answer::U128(u128::MAX)));
+        assert_eq!(parsed.parts(), vec![Part::Two]);
+    }
+
+    #[test]
+    fn skips_comments_but_not_hashes_in_strings() {
+        let text = "# Day 01\n\n[[case]] # first\nname = \"a # b\" # the name\n  # indented comment\n\
+                    file = 'c#d'\npart1 = 5 # answer\n";
+        let parsed = parse_one(text);
+        assert_eq!(parsed.name, "a # b");
+        assert_eq!(parsed.source, Source::File("c#d".to_string()));
+        assert_eq!(parsed.part1, Some(Answer::I64(5)));
+    }
+
+    #[test]
+    fn reads_params_as_text() {
+        let text = "[[case]]\nname = \"p\"\nfile = \"f\"\npart1 = 40\n\n[case.params]\n\
+                    connections = 10\nrequired = \"dac,fft\"\nlist = [1, 2]\n\n[[case]]\nname = \"q\"\nfile = \"f\"\n";
+        let cases = parse(text).unwrap_or_else(|e| panic!("{}", e));
+        let params: Vec<(&str, &str)> = cases[0].params.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect();
+        assert_eq!(params, [("connections", "10"), ("required", "dac,fft"), ("list", "1,2")]);
+        assert!(cases[1].params.is_empty());
+    }
+
+    #[test]
+    fn skips_cases_without_answers() {
+        let parsed = parse_one("[[case]]\nname = \"stub\"\nfile = \"day13_test.txt\"\n# part1 = 0\n");
+        assert!(parsed.parts().is_empty());
+    }
+
+    #[test]
+    fn rejects_malformed_values() {
+        assert_errors(&[
+            ("[[case]]\nname = \"\"\"x\"\"\"\n", "line 2: multi-line text must use ''' quotes"),
+            ("[[case]]\nname = \"open\n", "line 2: the string is never closed"),
+            ("[[case]]\nname = \"a\" b\n", "line 2: unexpected 'b' after the text"),
+            ("[[case]]\nname = \"\\q\"\n", "line 2: invalid escape \\q"),
+            ("[[case]]\nname = \"\\uZZZZ\"\n", "line 2: invalid escape \\uZZZZ"),
+            ("[[case]]\ninput = '''\nnever closed\n", "line 2: the ''' text is never closed"),
+            ("[[case]]\ninput = '''x''' y\n", "line 2: unexpected 'y' after the text"),
+            ("[[case]]\nparts = [1, x]\n", "line 2: expected a number in the list, found 'x'"),
+            ("[[case]]\npart1 = twelve\n", "line 2: expected a string, number or list, found 'twelve'"),
+        ]);
+    }
+
+    #[test]
+    fn rejects_misplaced_and_repeated_keys() {
+        assert_errors(&[
+            ("name = \"x\"\n", "line 1: settings before the first [[case]]"),
+            ("[case.params]\n", "line 1: unexpected table [case.params]"),
+            ("[[case]]\n[cases]\n", "line 2: unexpected table [cases]"),
+            ("[[case]]\nanswer = 1\n", "line 2: unknown key answer"),
+            ("[[case]]\nfoo # a = b\n", "line 2: expected `key = value`, found 'foo'"),
+            ("[[case]]\nname = \"a\"\nname = \"b\"\n", "line 3: name is set twice"),
+            ("[[case]]\n[case.params]\nk = 1\nk = 2\n", "line 4: parameter k is set twice"),
+        ]);
+    }
+
+    #[test]
+    fn rejects_incomplete_cases() {
+        assert_errors(&[
+            ("[[case]]\nfile = \"f\"\n", "case starting at line 1: missing name"),
+            ("[[case]]\nname = \"a\"\npart1 = 1\n", "case starting at line 1: a needs either a file or an input"),
+            (
+                "[[case]]\nname = \"a\"\nfile = \"f\"\ninput = \"x\"\n",
+                "case starting at line 1: a needs either a file or an input",
+            ),
+            (
+                "[[case]]\nname = \"a\"\nfile = \"f\"\nparts = [3]\n",
+                "case starting at line 1: a: invalid part 3, expected 1 or 2",
+            ),
+            (
+                "[[case]]\nname = \"a\"\nfile = \"f\"\nparts = 1\n",
+                "case starting at line 1: a: parts must be a list such as [1, 2]",
+            ),
+            (
+                "[[case]]\nname = \"a\"\nfile = \"f\"\nparts = [1, 2]\npart1 = 1\n",
+                "case starting at line 1: a runs part 2 but has no part2 answer",
+            ),
+            ("[[case]]\nname = \"a\"\nfile = \"f\"\n[[case]]\nname = \"a\"\nfile = \"g\"\n", "two cases are named a"),
+        ]);
+    }
+
+    #[test]
+    fn to_toml_reads_back_the_same_cases() {
+        let cases = vec![
+            Case { part1: Some(Answer::I64(5)), ..case("test", Source::File("day11_test.txt".to_string())) },
+            Case {
+                parts: Some(vec![Part::Two]),
+                part2: Some(Answer::U128(u128::MAX)),
+                params: vec![("connections".to_string(), "10".to_string()), ("you".to_string(), "a b".to_string())],
+                ..case("two \"branches\"", Source::Inline("you: a b\n# not a comment\n\ta: out\n".to_string()))
+            },
+            Case {
+                part1: Some(Answer::from("ABC")),
+                ..case("quotes", Source::Inline("x '''\\ y\n".to_string()))
+            },
+            case("stub", Source::File("day11_other.txt".to_string())),
+        ];
+        let toml = to_toml(11, "Reactor", &cases);
+        assert!(toml.starts_with("# Day 11: Reactor\n"));
+        assert_eq!(parse(&toml), Ok(cases));
+    }
+}
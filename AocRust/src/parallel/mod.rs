@@ -0,0 +1,178 @@
+use std::cell::Cell;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use crate::budget::Budget;
+use crate::error::SolveError;
+
+/// Worker threads used by [`jobs`]; 1 keeps everything on the calling thread
+static JOBS: AtomicUsize = AtomicUsize::new(1);
+
+thread_local! {
+    /// Set on the threads spawned by [`map`], so nested work stays sequential
+    /// instead of multiplying the thread count
+    static IN_WORKER: Cell<bool> = const { Cell::new(false) };
+
+    /// Index of the [`try_map`] item the thread is working on, 0 outside one
+    static ITEM: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Sets how many threads parallel solvers and runners may use (at least 1)
+pub fn set_jobs(jobs: usize) {
+    JOBS.store(jobs.max(1), Ordering::Relaxed);
+}
+
+/// Index of the [`try_map`] item being worked on by this thread, 0 outside one
+pub fn current_item() -> usize {
+    ITEM.with(Cell::get)
+}
+
+/// The `--jobs` setting, or 1 when called from inside a worker thread
+pub fn jobs() -> usize {
+    if IN_WORKER.with(Cell::get) { 1 } else { JOBS.load(Ordering::Relaxed) }
+}
+
+/// Applies `f` to every item on up to `jobs` scoped threads.
+/// Threads pick the next unclaimed item as they finish, so uneven items still
+/// balance, and the results come back in the order of `items` no matter which
+/// thread computed them.
+pub fn map<T, R, F>(items: &[T], jobs: usize, f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let threads = jobs.min(items.len());
+    if threads <= 1 {
+        return items.iter().map(f).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let mut results: Vec<Option<R>> = items.iter().map(|_| None).collect();
+
+    thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|_| {
+                scope.spawn(|| {
+                    IN_WORKER.with(|in_worker| in_worker.set(true));
+                    let mut done = Vec::new();
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(item) = items.get(index) else {
+                            break done;
+                        };
+                        done.push((index, f(item)));
+                    }
+                })
+            })
+            .collect();
+
+        for worker in workers {
+            let done = worker.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic));
+            for (index, result) in done {
+                results[index] = Some(result);
+            }
+        }
+    });
+
+    results
+        .into_iter()
+        .map(|result| result.expect("every item is claimed by a worker"))
+        .collect()
+}
+
+/// [`map`] for fallible units of work sharing one budget. An error cancels
+/// `budget` for the items after the failing one, so their searches stop at
+/// their next step and those not yet started are skipped, while earlier items
+/// run on. The error of the first item that fails is returned, never a
+/// cancellation it caused, so it does not depend on which thread was faster.
+pub fn try_map<T, R, F>(items: &[T], jobs: usize, budget: &Budget, f: F) -> Result<Vec<R>, SolveError>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> Result<R, SolveError> + Sync,
+{
+    let first_error: Mutex<Option<(usize, SolveError)>> = Mutex::new(None);
+    let indices: Vec<usize> = (0..items.len()).collect();
+    let results = map(&indices, jobs, |&index| {
+        let previous = ITEM.with(|item| item.replace(index));
+        let result = budget.check().and_then(|()| f(&items[index]));
+        ITEM.with(|item| item.set(previous));
+
+        if let Err(e) = &result {
+            let mut first_error = first_error.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            // Only items after the first failure are cancelled, so a cancellation
+            // never comes before the error that caused it
+            if first_error.as_ref().is_none_or(|&(first, _)| index < first) {
+                *first_error = Some((index, e.clone()));
+                budget.cancel_from(index + 1);
+            }
+        }
+        result
+    });
+
+    match first_error.into_inner().unwrap_or_else(|poisoned| poisoned.into_inner()) {
+        Some((_, e)) => Err(e),
+        None => results.into_iter().collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Item 1 fails only after a long search, item 3 right away
+    fn slow_then_fast(&item: &usize, budget: &Budget) -> Result<usize, SolveError> {
+        if item == 1 {
+            for _ in 0..2_000_000 {
+                budget.step()?;
+            }
+        }
+        if item == 1 || item == 3 {
+            return Err(SolveError::Unsolvable(format!("item {} failed", item)));
+        }
+        Ok(item * 10)
+    }
+
+    #[test]
+    fn map_keeps_the_order_of_items() {
+        let items: Vec<usize> = (0..100).collect();
+        for jobs in [1, 4] {
+            assert_eq!(map(&items, jobs, |&item| item * 2), items.iter().map(|item| item * 2).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn try_map_collects_results_when_nothing_fails() {
+        let budget = Budget::unlimited();
+        assert_eq!(try_map(&[0, 2, 4], 3, &budget, |&item| Ok(item + 1)), Ok(vec![1, 3, 5]));
+    }
+
+    #[test]
+    fn try_map_reports_the_first_item_that_fails_whichever_finishes_first() {
+        let items: Vec<usize> = (0..8).collect();
+        for jobs in [1, 2, 4, 8] {
+            let budget = Budget::unlimited();
+            let result = try_map(&items, jobs, &budget, |item| slow_then_fast(item, &budget));
+            assert_eq!(result, Err(SolveError::Unsolvable("item 1 failed".to_string())), "jobs {}", jobs);
+        }
+    }
+
+    #[test]
+    fn try_map_only_cancels_items_after_the_failure() {
+        let budget = Budget::unlimited();
+        budget.cancel_from(2);
+        assert_eq!(budget.check(), Ok(()));
+        let result = try_map(&[0, 1, 2, 3], 1, &budget, |&item| budget.check().map(|()| item));
+        assert_eq!(result, Err(SolveError::TimedOut("cancelled".to_string())));
+        let started = Mutex::new(Vec::new());
+        let _ = try_map(&[0, 1, 2, 3], 1, &budget, |&item| {
+            started.lock().unwrap().push(item);
+            Ok(item)
+        });
+        assert_eq!(started.into_inner().unwrap(), [0, 1]);
+        budget.cancel();
+        assert!(budget.check().is_err());
+    }
+}
@@ -0,0 +1,65 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// Why a parameter could not be set
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParamsError(pub String);
+
+impl fmt::Display for ParamsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for ParamsError {}
+
+/// A day's tunable values: numbers and names the puzzle text fixes, such as
+/// the dial size or how many pairs to connect, but that an example (or an
+/// experiment) may change. `Default` holds the values the puzzle uses.
+pub trait Params: Default + fmt::Debug + 'static {
+    /// Every parameter name, in the order they are documented
+    const NAMES: &'static [&'static str];
+
+    /// Sets the parameter `name` from its text
+    fn set(&mut self, name: &str, value: &str) -> Result<(), ParamsError>;
+
+    /// Checks the values against each other once all of them are set
+    fn validate(&self) -> Result<(), ParamsError> {
+        Ok(())
+    }
+}
+
+/// Days without parameters
+impl Params for () {
+    const NAMES: &'static [&'static str] = &[];
+
+    fn set(&mut self, name: &str, _value: &str) -> Result<(), ParamsError> {
+        Err(unknown(name, Self::NAMES))
+    }
+}
+
+/// The puzzle's values with `overrides` applied in order
+pub fn with_overrides<P: Params>(overrides: &[(String, String)]) -> Result<P, ParamsError> {
+    let mut params = P::default();
+    for (name, value) in overrides {
+        params.set(name, value)?;
+    }
+    params.validate()?;
+    Ok(params)
+}
+
+/// Reads a parameter's value, naming the parameter when it does not parse
+pub fn value<T: FromStr>(name: &str, value: &str) -> Result<T, ParamsError> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| ParamsError(format!("invalid value '{}' for parameter {}", value, name)))
+}
+
+/// The error for a parameter the day does not have, listing the ones it does
+pub fn unknown(name: &str, names: &[&str]) -> ParamsError {
+    match names {
+        [] => ParamsError(format!("unknown parameter {}, the day takes no parameters", name)),
+        names => ParamsError(format!("unknown parameter {}, expected one of: {}", name, names.join(", "))),
+    }
+}
@@ -0,0 +1,28 @@
+use crate::solution::DynSolution;
+use crate::{day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12};
+
+/// Every implemented day, in order. Add new days here as you implement them.
+static DAYS: &[&dyn DynSolution] = &[
+    &day01::Day01,
+    &day02::Day02,
+    &day03::Day03,
+    &day04::Day04,
+    &day05::Day05,
+    &day06::Day06,
+    &day07::Day07,
+    &day08::Day08,
+    &day09::Day09,
+    &day10::Day10,
+    &day11::Day11,
+    &day12::Day12,
+];
+
+/// Returns all registered days, sorted by day number
+pub fn all() -> &'static [&'static dyn DynSolution] {
+    DAYS
+}
+
+/// Looks up a single day
+pub fn get(day: u32) -> Option<&'static dyn DynSolution> {
+    DAYS.iter().copied().find(|solution| solution.day() == day)
+}
@@ -0,0 +1,590 @@
+use std::fmt;
+use std::time::Duration;
+
+use crate::answer::Answer;
+use crate::error::Error;
+use crate::memory::AllocStats;
+use crate::registry;
+use crate::solution::Part;
+use crate::utils::{format_bytes, format_duration};
+
+/// How a part's answer compared with what was expected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail,
+    /// The input could not be parsed or the part could not be solved
+    Error,
+    /// No recorded answer or no real input to check against
+    Missing,
+    /// An answer was produced but there was nothing to compare it with
+    Solved,
+    /// The solver ran out of its time or step budget, so the answer is unknown
+    TimedOut,
+}
+
+impl Status {
+    /// Whether this outcome should fail a run
+    pub fn is_failure(self) -> bool {
+        matches!(self, Status::Fail | Status::Error | Status::TimedOut)
+    }
+
+    /// The inverse of the lowercase name used in JSON and CSV
+    pub fn from_name(name: &str) -> Option<Status> {
+        [Status::Pass, Status::Fail, Status::Error, Status::Missing, Status::Solved, Status::TimedOut]
+            .into_iter()
+            .find(|status| status.name() == name)
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Status::Pass => "pass",
+            Status::Fail => "fail",
+            Status::Error => "error",
+            Status::Missing => "missing",
+            Status::Solved => "solved",
+            Status::TimedOut => "timeout",
+        }
+    }
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(&self.name().to_uppercase())
+    }
+}
+
+/// Where the input of a record came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputKind {
+    /// An example case from the day's `dayNN.cases.toml`
+    Example,
+    /// The day's `dayNN.txt` puzzle input
+    Real,
+    /// A file or stdin given on the command line
+    Custom,
+}
+
+impl InputKind {
+    pub fn from_name(name: &str) -> Option<InputKind> {
+        [InputKind::Example, InputKind::Real, InputKind::Custom]
+            .into_iter()
+            .find(|kind| kind.name() == name)
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            InputKind::Example => "example",
+            InputKind::Real => "real",
+            InputKind::Custom => "custom",
+        }
+    }
+}
+
+impl fmt::Display for InputKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+/// Result of running one solver: its answer or error, its timing and how it compared
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    pub answer: Option<Answer>,
+    pub expected: Option<Answer>,
+    pub error: Option<String>,
+    pub duration: Duration,
+    pub status: Status,
+    /// What the solver allocated, when built with the `alloc-stats` feature
+    pub memory: Option<AllocStats>,
+}
+
+impl Outcome {
+    /// Compares an answer (or error) with the expected one, if any
+    pub fn new<E: Into<Error>>(result: Result<Answer, E>, expected: Option<Answer>, duration: Duration) -> Self {
+        match result {
+            Ok(answer) => {
+                let status = match &expected {
+                    Some(expected) if *expected == answer => Status::Pass,
+                    Some(_) => Status::Fail,
+                    None => Status::Solved,
+                };
+                Outcome { answer: Some(answer), expected, error: None, duration, status, memory: None }
+            }
+            Err(e) => {
+                let e = e.into();
+                let status = if e.is_timeout() { Status::TimedOut } else { Status::Error };
+                Outcome { answer: None, expected, error: Some(e.to_string()), duration, status, memory: None }
+            }
+        }
+    }
+
+    /// An outcome for a part that could not run, e.g. because its input is unreadable
+    pub fn error(error: impl fmt::Display, expected: Option<Answer>) -> Self {
+        Outcome {
+            answer: None,
+            expected,
+            error: Some(error.to_string()),
+            duration: Duration::ZERO,
+            status: Status::Error,
+            memory: None,
+        }
+    }
+}
+
+/// One part run against one input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub day: u32,
+    pub part: Part,
+    pub input: InputKind,
+    pub path: String,
+    pub outcome: Outcome,
+}
+
+/// Machine-readable serializations of a whole run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Csv,
+    Junit,
+}
+
+impl Format {
+    pub fn from_name(name: &str) -> Option<Format> {
+        match name {
+            "json" => Some(Format::Json),
+            "csv" => Some(Format::Csv),
+            "junit" => Some(Format::Junit),
+            _ => None,
+        }
+    }
+
+    pub fn render(self, records: &[Record]) -> String {
+        match self {
+            Format::Json => to_json(records),
+            Format::Csv => to_csv(records),
+            Format::Junit => to_junit(records),
+        }
+    }
+}
+
+/// Counts of passed, failed, errored (including timed out) and remaining records
+fn tally<'a>(records: impl IntoIterator<Item = &'a Record>) -> (usize, usize, usize, usize) {
+    let mut counts = (0, 0, 0, 0);
+    for record in records {
+        match record.outcome.status {
+            Status::Pass => counts.0 += 1,
+            Status::Fail => counts.1 += 1,
+            Status::Error | Status::TimedOut => counts.2 += 1,
+            Status::Missing | Status::Solved => counts.3 += 1,
+        }
+    }
+    counts
+}
+
+fn total_duration<'a>(records: impl IntoIterator<Item = &'a Record>) -> Duration {
+    records.into_iter().map(|record| record.outcome.duration).sum()
+}
+
+/// `{"passed": .., "records": [{"day": 1, "part": 1, "input": "real", ...}]}`.
+/// Answers are strings so 128-bit values survive JSON parsers that use doubles.
+pub fn to_json(records: &[Record]) -> String {
+    let (passed, failed, errors, other) = tally(records);
+    let mut json = format!(
+        "{{\n  \"passed\": {},\n  \"failed\": {},\n  \"errors\": {},\n  \"unchecked\": {},\n  \"duration_ns\": {},\n  \"records\": [",
+        passed,
+        failed,
+        errors,
+        other,
+        total_duration(records).as_nanos()
+    );
+
+    for (i, record) in records.iter().enumerate() {
+        let outcome = &record.outcome;
+        json.push_str(if i == 0 { "\n" } else { ",\n" });
+        json.push_str(&format!(
+            "    {{\"day\": {}, \"part\": {}, \"input\": \"{}\", \"path\": {}, \"answer\": {}, \"expected\": {}, \"status\": \"{}\", \"duration_ns\": {}, \"error\": {}, \"allocations\": {}, \"allocated_bytes\": {}, \"peak_bytes\": {}}}",
+            record.day,
+            record.part.number(),
+            record.input,
+            json_string(Some(&record.path)),
+            json_string(outcome.answer.as_ref().map(Answer::to_string).as_deref()),
+            json_string(outcome.expected.as_ref().map(Answer::to_string).as_deref()),
+            outcome.status.name(),
+            outcome.duration.as_nanos(),
+            json_string(outcome.error.as_deref()),
+            json_number(outcome.memory.map(|memory| memory.allocations)),
+            json_number(outcome.memory.map(|memory| memory.bytes)),
+            json_number(outcome.memory.map(|memory| memory.peak)),
+        ));
+    }
+
+    json.push_str(if records.is_empty() { "]\n}\n" } else { "\n  ]\n}\n" });
+    json
+}
+
+fn json_number(value: Option<u64>) -> String {
+    value.map_or_else(|| "null".to_string(), |value| value.to_string())
+}
+
+fn json_string(value: Option<&str>) -> String {
+    let Some(value) = value else {
+        return "null".to_string();
+    };
+
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+/// One header row plus one row per record
+pub fn to_csv(records: &[Record]) -> String {
+    let mut csv =
+        String::from("day,part,input,path,answer,expected,status,duration_ns,error,allocations,allocated_bytes,peak_bytes\n");
+    for record in records {
+        let outcome = &record.outcome;
+        let fields = [
+            record.day.to_string(),
+            record.part.number().to_string(),
+            record.input.to_string(),
+            record.path.clone(),
+            outcome.answer.as_ref().map(Answer::to_string).unwrap_or_default(),
+            outcome.expected.as_ref().map(Answer::to_string).unwrap_or_default(),
+            outcome.status.name().to_string(),
+            outcome.duration.as_nanos().to_string(),
+            outcome.error.clone().unwrap_or_default(),
+            outcome.memory.map(|memory| memory.allocations.to_string()).unwrap_or_default(),
+            outcome.memory.map(|memory| memory.bytes.to_string()).unwrap_or_default(),
+            outcome.memory.map(|memory| memory.peak.to_string()).unwrap_or_default(),
+        ];
+        let row: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Reads records back from [`to_csv`] output, e.g. from another `aoc_rust` process
+pub fn from_csv(csv: &str) -> Result<Vec<Record>, String> {
+    let mut rows = csv_rows(csv).into_iter();
+    match rows.next() {
+        Some(header) if header.first().is_some_and(|name| name == "day") => {}
+        _ => return Err("expected a CSV header starting with 'day'".to_string()),
+    }
+
+    rows.enumerate()
+        .map(|(index, row)| {
+            let invalid = || format!("invalid CSV record on row {}: {}", index + 2, row.join(","));
+            let [day, part, input, path, answer, expected, status, duration, error, allocations, bytes, peak] =
+                <[String; 12]>::try_from(row.clone()).map_err(|_| invalid())?;
+            let answer_of = |value: &str| (!value.is_empty()).then(|| value.parse::<Answer>().unwrap_or_else(|e| match e {}));
+            let memory = match (allocations.parse(), bytes.parse(), peak.parse()) {
+                (Ok(allocations), Ok(bytes), Ok(peak)) => Some(AllocStats { allocations, bytes, peak }),
+                _ => None,
+            };
+
+            Ok(Record {
+                day: day.parse().map_err(|_| invalid())?,
+                part: part.parse().ok().and_then(Part::from_number).ok_or_else(invalid)?,
+                input: InputKind::from_name(&input).ok_or_else(invalid)?,
+                path,
+                outcome: Outcome {
+                    answer: answer_of(&answer),
+                    expected: answer_of(&expected),
+                    error: (!error.is_empty()).then_some(error),
+                    duration: Duration::from_nanos(duration.parse().map_err(|_| invalid())?),
+                    status: Status::from_name(&status).ok_or_else(invalid)?,
+                    memory,
+                },
+            })
+        })
+        .collect()
+}
+
+/// Splits CSV text into rows of unquoted fields, skipping empty lines
+fn csv_rows(csv: &str) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = csv.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => row.push(std::mem::take(&mut field)),
+            '\n' if !quoted => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            '\r' if !quoted => {}
+            c => field.push(c),
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+
+    rows.retain(|row| row.len() > 1 || row.first().is_some_and(|field| !field.is_empty()));
+    rows
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// JUnit XML with one test suite per day and one test case per record.
+/// Records with nothing to compare against are reported as skipped.
+pub fn to_junit(records: &[Record]) -> String {
+    let (_, failed, errors, other) = tally(records);
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<testsuites name=\"aoc_rust\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{:.6}\">\n",
+        records.len(),
+        failed,
+        errors,
+        other,
+        total_duration(records).as_secs_f64()
+    ));
+
+    let mut days: Vec<u32> = records.iter().map(|record| record.day).collect();
+    days.sort_unstable();
+    days.dedup();
+    for day in days {
+        let suite: Vec<&Record> = records.iter().filter(|record| record.day == day).collect();
+        let (_, failed, errors, other) = tally(suite.iter().copied());
+        let title = registry::get(day).map(|solution| solution.title()).unwrap_or_default();
+        xml.push_str(&format!(
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{:.6}\">\n",
+            xml_escape(&format!("Day {:02}: {}", day, title)),
+            suite.len(),
+            failed,
+            errors,
+            other,
+            total_duration(suite.iter().copied()).as_secs_f64()
+        ));
+
+        for record in suite {
+            let outcome = &record.outcome;
+            xml.push_str(&format!(
+                "    <testcase classname=\"day{:02}\" name=\"{}\" time=\"{:.6}\"",
+                day,
+                xml_escape(&format!("{} ({} input {})", record.part, record.input, record.path)),
+                outcome.duration.as_secs_f64()
+            ));
+            let answer = outcome.answer.as_ref().map(Answer::to_string).unwrap_or_default();
+            let detail = match outcome.status {
+                Status::Pass => None,
+                Status::Fail => Some(format!(
+                    "<failure message=\"{}\"/>",
+                    xml_escape(&format!(
+                        "expected {}, got {}",
+                        outcome.expected.as_ref().map(Answer::to_string).unwrap_or_default(),
+                        answer
+                    ))
+                )),
+                Status::Error => Some(format!(
+                    "<error message=\"{}\"/>",
+                    xml_escape(outcome.error.as_deref().unwrap_or_default())
+                )),
+                Status::TimedOut => Some(format!(
+                    "<error type=\"timeout\" message=\"{}\"/>",
+                    xml_escape(outcome.error.as_deref().unwrap_or_default())
+                )),
+                Status::Missing | Status::Solved => Some(format!(
+                    "<skipped message=\"{}\"/>",
+                    xml_escape(&format!("no expected answer; got {}", answer))
+                )),
+            };
+            match detail {
+                Some(detail) => xml.push_str(&format!(">\n      {}\n    </testcase>\n", detail)),
+                None => xml.push_str("/>\n"),
+            }
+        }
+        xml.push_str("  </testsuite>\n");
+    }
+
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// A Markdown table with one row per day: its title, real-input answers,
+/// example results and solve times, followed by the total runtime
+pub fn summary_table(records: &[Record], total: Duration) -> String {
+    let mut days: Vec<u32> = records.iter().map(|record| record.day).collect();
+    days.sort_unstable();
+    days.dedup();
+
+    let header = ["Day", "Title", "Part 1", "Part 2", "Examples", "Part 1 time", "Part 2 time"];
+    let mut rows: Vec<[String; 7]> = Vec::new();
+    for day in days {
+        let real = |part: Part| {
+            records
+                .iter()
+                .find(|record| record.day == day && record.part == part && record.input != InputKind::Example)
+        };
+        let answer = |part: Part| match real(part).map(|record| &record.outcome) {
+            Some(Outcome { answer: Some(answer), .. }) => answer.to_string(),
+            Some(outcome) => outcome.status.to_string(),
+            None => "-".to_string(),
+        };
+        let time = |part: Part| match real(part) {
+            Some(record) if record.outcome.answer.is_some() => match record.outcome.memory {
+                Some(memory) => format!("{} (peak {})", format_duration(record.outcome.duration), format_bytes(memory.peak)),
+                None => format_duration(record.outcome.duration),
+            },
+            _ => "-".to_string(),
+        };
+
+        let examples: Vec<&Record> = records
+            .iter()
+            .filter(|record| record.day == day && record.input == InputKind::Example)
+            .collect();
+        let passed = examples.iter().filter(|record| record.outcome.status == Status::Pass).count();
+        let example_status = match examples.len() {
+            0 => "-".to_string(),
+            n if passed == n => format!("✓ {}/{}", passed, n),
+            n => format!("✗ {}/{}", passed, n),
+        };
+
+        let title = registry::get(day).map(|solution| solution.title()).unwrap_or_default();
+        rows.push([
+            day.to_string(),
+            title.to_string(),
+            answer(Part::One),
+            answer(Part::Two),
+            example_status,
+            time(Part::One),
+            time(Part::Two),
+        ]);
+    }
+
+    let mut widths = header.map(|title| title.chars().count());
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let line = |cells: Vec<String>| {
+        let padded: Vec<String> = cells
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect();
+        format!("| {} |\n", padded.join(" | "))
+    };
+
+    let mut table = line(header.iter().map(|title| title.to_string()).collect());
+    table.push_str(&line(widths.iter().map(|&width| "-".repeat(width)).collect()));
+    for row in rows {
+        table.push_str(&line(row.to_vec()));
+    }
+    table.push_str(&format!("\nTotal runtime: {}\n", format_duration(total)));
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(day: u32, part: Part, input: InputKind, path: &str, outcome: Outcome) -> Record {
+        Record { day, part, input, path: path.to_string(), outcome }
+    }
+
+    fn records() -> Vec<Record> {
+        let wide = Answer::from(u128::MAX);
+        let mut pass = Outcome::new(Ok::<_, Error>(wide.clone()), Some(wide), Duration::from_nanos(1_234_567));
+        pass.memory = Some(AllocStats { allocations: 3, bytes: 4096, peak: 1024 });
+        vec![
+            record(1, Part::One, InputKind::Real, "inputs/day01.txt", pass),
+            record(
+                2,
+                Part::Two,
+                InputKind::Example,
+                "inputs/a \"quoted\", odd\npath.txt",
+                Outcome::error("line 3, column 5: expected \"x\", found ','\r\nsecond line", None),
+            ),
+            record(
+                3,
+                Part::One,
+                InputKind::Custom,
+                "-",
+                Outcome::new(Ok::<_, Error>(Answer::from("A,B")), Some(Answer::from(-7i64)), Duration::ZERO),
+            ),
+        ]
+    }
+
+    #[test]
+    fn reads_back_what_to_csv_writes() {
+        let records = records();
+        let csv = to_csv(&records);
+        assert_eq!(from_csv(&csv), Ok(records));
+    }
+
+    #[test]
+    fn quotes_only_fields_that_need_it() {
+        let csv = to_csv(&records());
+        let mut lines = csv.lines().skip(1);
+        assert_eq!(
+            lines.next(),
+            Some(concat!(
+                "1,1,real,inputs/day01.txt,340282366920938463463374607431768211455,",
+                "340282366920938463463374607431768211455,pass,1234567,,3,4096,1024"
+            ))
+        );
+        assert_eq!(lines.next(), Some("2,2,example,\"inputs/a \"\"quoted\"\", odd"));
+        assert_eq!(from_csv(&csv).unwrap()[2].outcome.memory, None);
+    }
+
+    #[test]
+    fn rejects_csv_without_its_header_or_with_short_rows() {
+        assert!(from_csv("").is_err());
+        assert!(from_csv("1,1,real\n").is_err());
+        let error = from_csv("day,part\n1,1,real\n").unwrap_err();
+        assert!(error.contains("row 2"), "{}", error);
+        assert_eq!(from_csv("day,part\n\n"), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn escapes_json_strings() {
+        assert_eq!(json_string(None), "null");
+        assert_eq!(json_string(Some("a \"b\" \\ c\nd\r\te\u{1}")), "\"a \\\"b\\\" \\\\ c\\nd\\r\\te\\u0001\"");
+
+        let json = to_json(&records());
+        assert!(json.contains("\"answer\": \"340282366920938463463374607431768211455\""), "{}", json);
+        assert!(json.contains("\"path\": \"inputs/a \\\"quoted\\\", odd\\npath.txt\""), "{}", json);
+        assert!(json.contains("\"allocations\": null"), "{}", json);
+        assert!(json.contains("\"passed\": 1,\n  \"failed\": 1,\n  \"errors\": 1"), "{}", json);
+    }
+}
@@ -0,0 +1,201 @@
+use std::any::Any;
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::Instant;
+
+use crate::inputs::Inputs;
+use crate::manifest::{self, Source};
+use crate::{parallel, registry};
+use crate::report::{self, InputKind, Outcome, Record};
+use crate::solution::{DynSolution, Part};
+use crate::utils::{self, format_duration};
+
+/// `print!` that only writes when the run is echoed to the console
+macro_rules! echo {
+    ($echo:expr, $($arg:tt)*) => {
+        if $echo {
+            print!($($arg)*);
+            let _ = io::stdout().flush();
+        }
+    };
+}
+
+/// Runs a day the way the interactive menu does: check every example case, then
+/// solve the real input. Each input is parsed once and shared by both parts,
+/// and parse time is reported separately from each part's solve time.
+/// Progress is printed when `echo` is set; either way a [`Record`] is returned
+/// for every part run against every input that was found.
+pub fn run_day(solution: &dyn DynSolution, inputs: &Inputs, echo: bool) -> Vec<Record> {
+    let day = solution.day();
+    let mut records = run_examples(solution, inputs, echo);
+
+    let real_path = inputs.real(day);
+    echo!(echo, "\n=== Real Input ===\n");
+    if !real_path.exists() {
+        echo!(echo, "⚠ Real input file not found: {}\n", real_path.display());
+        echo!(
+            echo,
+            "  Please download your puzzle input from https://adventofcode.com/2025/day/{}/input\n",
+            day
+        );
+    } else {
+        let parsed = parse_file(solution, &real_path, echo);
+        let params = solution.params(&[]).expect("the puzzle's own parameters are valid");
+        for part in Part::BOTH.into_iter().filter(|&part| solution.has_part(part)) {
+            let outcome = match &parsed {
+                Ok(parsed) => {
+                    echo!(echo, "Running {} (Real Input)... ", part);
+                    let outcome = utils::check(
+                        || solution.solve(part, parsed.as_ref(), params.as_ref(), &solution.budget(params.as_ref())),
+                        None,
+                    );
+                    if echo {
+                        utils::print_real_outcome(&outcome);
+                    }
+                    outcome
+                }
+                Err(e) => Outcome::error(e, None),
+            };
+            records.push(record(day, part, InputKind::Real, &real_path, outcome));
+        }
+    }
+
+    if !solution.has_part(Part::Two) {
+        echo!(echo, "\n🎄 Part 2 automatically completed! Both stars earned! 🎄\n\n");
+    }
+
+    records
+}
+
+/// Checks the example cases of a day's manifest (see [`manifest::day_cases`]);
+/// a case whose input file is missing fails each of its parts.
+/// Prints progress when `echo` is set and returns a [`Record`] per part run.
+pub fn run_examples(solution: &dyn DynSolution, inputs: &Inputs, echo: bool) -> Vec<Record> {
+    let day = solution.day();
+    let mut records = Vec::new();
+
+    let cases = match manifest::day_cases(day, inputs) {
+        Ok(cases) => cases,
+        Err(e) => {
+            echo!(echo, "\n✗ ERROR: {}\n", e);
+            let path = inputs.cases(day);
+            records.push(record(day, Part::One, InputKind::Example, &path, Outcome::error(e, None)));
+            Vec::new()
+        }
+    };
+    for case in cases {
+        let parts: Vec<Part> = case.parts().into_iter().filter(|&part| solution.has_part(part)).collect();
+        if parts.is_empty() {
+            continue;
+        }
+        let (label, input) = match &case.source {
+            Source::File(file) => {
+                let path = inputs.root().join(file);
+                (path.display().to_string(), utils::read_input(&path).map_err(|e| e.to_string()))
+            }
+            Source::Inline(input) => {
+                (format!("{} ({})", inputs.cases(day).display(), case.name), Ok(input.clone()))
+            }
+        };
+
+        echo!(echo, "\n=== Test Input ({}) ===\n", label);
+        // The case's parameters are checked first, so a typo fails without parsing anything
+        let prepared = input.and_then(|input| {
+            let params = solution.params(&case.params).map_err(|e| format!("{}: {}", label, e))?;
+            Ok((input, params))
+        });
+        let parsed = match prepared {
+            Ok((input, params)) => parse_text(solution, &input, &label, echo).map(|parsed| (parsed, params)),
+            Err(e) => {
+                echo!(echo, "ERROR: {}\n", e);
+                Err(e)
+            }
+        };
+        for part in parts {
+            let expected = case.expected(part).cloned();
+            let outcome = match &parsed {
+                Ok((parsed, params)) if echo => utils::run_test(
+                    &format!("{} (Test)", part),
+                    || solution.solve(part, parsed.as_ref(), params.as_ref(), &solution.budget(params.as_ref())),
+                    expected.expect("a case has an answer for each of its parts"),
+                ),
+                Ok((parsed, params)) => utils::check(
+                    || solution.solve(part, parsed.as_ref(), params.as_ref(), &solution.budget(params.as_ref())),
+                    expected,
+                ),
+                Err(e) => Outcome::error(e, expected),
+            };
+            records.push(Record { day, part, input: InputKind::Example, path: label.clone(), outcome });
+        }
+    }
+
+    records
+}
+
+/// Runs every registered day against its examples and real input, then prints
+/// a summary table of answers, example results and timings.
+/// With more than one job the days run concurrently and only the table is
+/// printed, since their progress would interleave.
+/// Returns the records of the whole run.
+pub fn run_all(inputs: &Inputs) -> Vec<Record> {
+    let start = Instant::now();
+    let jobs = parallel::jobs();
+    let mut records = Vec::new();
+
+    if jobs > 1 {
+        println!("\nRunning {} days on {} threads...", registry::all().len(), jobs);
+        records = run_days(registry::all(), inputs, jobs);
+    } else {
+        for solution in registry::all() {
+            println!("\n--- Day {}: {} ---", solution.day(), solution.title());
+            records.extend(run_day(*solution, inputs, true));
+        }
+    }
+
+    println!("\n{}", report::summary_table(&records, start.elapsed()));
+    records
+}
+
+/// Runs the given days quietly on up to `jobs` threads; the records keep the
+/// order of `solutions`
+pub fn run_days(solutions: &[&dyn DynSolution], inputs: &Inputs, jobs: usize) -> Vec<Record> {
+    parallel::map(solutions, jobs, |solution| run_day(*solution, inputs, false))
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+fn record(day: u32, part: Part, input: InputKind, path: &Path, outcome: Outcome) -> Record {
+    Record { day, part, input, path: path.display().to_string(), outcome }
+}
+
+/// Reads and parses an input file, printing the parse time or the reason it failed
+fn parse_file(solution: &dyn DynSolution, path: &Path, echo: bool) -> Result<Box<dyn Any>, String> {
+    match utils::read_input(path) {
+        Ok(input) => parse_text(solution, &input, &path.display().to_string(), echo),
+        Err(e) => {
+            echo!(echo, "ERROR: {}\n", e);
+            Err(e.to_string())
+        }
+    }
+}
+
+/// Parses an input, printing the parse time or the reason it failed; `label`
+/// says where the input came from
+fn parse_text(solution: &dyn DynSolution, input: &str, label: &str, echo: bool) -> Result<Box<dyn Any>, String> {
+    echo!(echo, "Parsing... ");
+    let start = Instant::now();
+    let parsed = solution.parse(input);
+    let elapsed = format_duration(start.elapsed());
+    match parsed {
+        Ok(parsed) => {
+            echo!(echo, "done [{}]\n", elapsed);
+            Ok(parsed)
+        }
+        Err(e) => {
+            let message = format!("{}: {}", label, e);
+            echo!(echo, "ERROR: {} [{}]\n", message, elapsed);
+            Err(message)
+        }
+    }
+}
@@ -0,0 +1,295 @@
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::inputs::Inputs;
+use crate::manifest::{self, Case, Source};
+use crate::registry;
+
+/// rustfmt's default line width, which the generated code keeps to
+const MAX_WIDTH: usize = 100;
+
+/// Why a day could not be scaffolded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScaffoldError(pub String);
+
+impl fmt::Display for ScaffoldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for ScaffoldError {}
+
+/// What [`new_day`] did to each file it touched
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    Created(PathBuf),
+    Updated(PathBuf),
+    /// Left alone because it already existed, e.g. an input that was already downloaded
+    Kept(PathBuf),
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Change::Created(path) => write!(f, "created {}", path.display()),
+            Change::Updated(path) => write!(f, "updated {}", path.display()),
+            Change::Kept(path) => write!(f, "kept    {} (already exists)", path.display()),
+        }
+    }
+}
+
+/// Generates a day the way the existing ones are laid out: `src/dayNN/mod.rs`
+/// with a [`Solution`](crate::solution::Solution) skeleton, its `pub mod` in
+/// `src/lib.rs`, entries in `src/registry/mod.rs` and `tests/puzzles.rs`,
+/// empty `dayNN.txt` and `dayNN_test.txt` inputs, a `dayNN.cases.toml` manifest
+/// and a `docs/problems/dayNN.md` stub. The title is taken from `title`, else
+/// from an existing problem description. Fails without touching anything if the
+/// day's module already exists.
+pub fn new_day(day: u32, title: Option<&str>, inputs: &Inputs) -> Result<Vec<Change>, ScaffoldError> {
+    let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let module = format!("day{:02}", day);
+    let source = crate_dir.join("src").join(&module).join("mod.rs");
+    let lib = crate_dir.join("src").join("lib.rs");
+    let registry = crate_dir.join("src").join("registry").join("mod.rs");
+    let tests = crate_dir.join("tests").join("puzzles.rs");
+    let problem = inputs.problem(day);
+
+    if source.exists() || registry::get(day).is_some() {
+        return Err(ScaffoldError(format!("day {} already exists: {}", day, source.display())));
+    }
+    let lib_text = read(&lib)?;
+    let registry_text = read(&registry)?;
+    let title = match title {
+        Some(title) => title.to_string(),
+        None => fs::read_to_string(&problem)
+            .ok()
+            .and_then(|markdown| problem_title(&markdown))
+            .unwrap_or_else(|| "TODO".to_string()),
+    };
+
+    // Work out every edit before writing, so a failure leaves the tree as it was
+    let lib_text = register_module(&lib_text, day)?;
+    let registry_text = register_solution(&registry_text, day)?;
+    let tests_text = match fs::read_to_string(&tests) {
+        Ok(text) => Some(register_test(&text, day)?),
+        Err(_) => None,
+    };
+
+    let mut changes = Vec::new();
+    fs::create_dir_all(source.parent().unwrap_or(crate_dir)).map_err(|e| write_error(&source, e))?;
+    write(&source, &module_source(day, &title))?;
+    changes.push(Change::Created(source));
+    write(&lib, &lib_text)?;
+    changes.push(Change::Updated(lib));
+    write(&registry, &registry_text)?;
+    changes.push(Change::Updated(registry));
+    if let Some(tests_text) = tests_text {
+        write(&tests, &tests_text)?;
+        changes.push(Change::Updated(tests));
+    }
+
+    for path in [inputs.real(day), inputs.named(day, "test")] {
+        changes.push(create_if_missing(&path, "")?);
+    }
+    changes.push(create_if_missing(&inputs.cases(day), &manifest_stub(day, &title))?);
+    if let Some(dir) = problem.parent() {
+        fs::create_dir_all(dir).map_err(|e| write_error(dir, e))?;
+    }
+    changes.push(create_if_missing(&problem, &problem_stub(day, &title))?);
+
+    Ok(changes)
+}
+
+/// The title in a problem description's `# Day N: Title` heading
+pub fn problem_title(markdown: &str) -> Option<String> {
+    let heading = markdown.lines().next()?.strip_prefix("# Day ")?;
+    let (_, title) = heading.split_once(':')?;
+    Some(title.trim().to_string()).filter(|title| !title.is_empty())
+}
+
+/// `lib.rs` with `pub mod dayNN;` added among the other days, in day order
+fn register_module(lib: &str, day: u32) -> Result<String, ScaffoldError> {
+    insert_in_order(lib, day, &format!("pub mod day{:02};", day), |line| {
+        line.strip_prefix("pub mod day")?.strip_suffix(';')?.parse().ok()
+    })
+    .ok_or_else(|| ScaffoldError("no `pub mod dayNN;` lines found in src/lib.rs".to_string()))
+}
+
+/// The registry with the day added to its `use crate::{...}` line and its `DAYS` list
+fn register_solution(registry: &str, day: u32) -> Result<String, ScaffoldError> {
+    let missing = |what: &str| ScaffoldError(format!("no {} found in src/registry/mod.rs", what));
+    let entry = format!("    &day{0:02}::Day{0:02},", day);
+    let registry = insert_in_order(registry, day, &entry, |line| {
+        line.trim().strip_prefix("&day")?.split_once("::")?.0.parse().ok()
+    })
+    .ok_or_else(|| missing("`&dayNN::DayNN,` entries"))?;
+
+    // The import is a single line until rustfmt wraps it, so read it either way
+    let start = registry
+        .find("use crate::{day")
+        .or_else(|| registry.find("use crate::{\n"))
+        .ok_or_else(|| missing("`use crate::{dayNN, ...};` line"))?;
+    let end = start + registry[start..].find("};").ok_or_else(|| missing("end of the `use crate::{...}` line"))? + 2;
+    let mut days: Vec<u32> = registry[start + "use crate::{".len()..end - 2]
+        .split(',')
+        .filter_map(|module| module.trim().strip_prefix("day")?.parse().ok())
+        .collect();
+    days.push(day);
+    days.sort_unstable();
+    days.dedup();
+    let modules: Vec<String> = days.iter().map(|day| format!("day{:02}", day)).collect();
+
+    Ok(format!("{}{}{}", &registry[..start], import_list(&modules), &registry[end..]))
+}
+
+/// `tests/puzzles.rs` with a `dayNN => N,` entry added to its `day_tests!` list
+fn register_test(tests: &str, day: u32) -> Result<String, ScaffoldError> {
+    insert_in_order(tests, day, &format!("    day{:02} => {},", day, day), |line| {
+        line.trim().strip_suffix(',')?.split_once(" => ")?.1.parse().ok()
+    })
+    .ok_or_else(|| ScaffoldError("no `dayNN => N,` entries found in tests/puzzles.rs".to_string()))
+}
+
+/// `use crate::{...};` laid out the way rustfmt would: on one line while it fits,
+/// else one indented line per row of modules
+fn import_list(modules: &[String]) -> String {
+    let single = format!("use crate::{{{}}};", modules.join(", "));
+    if single.len() <= MAX_WIDTH {
+        return single;
+    }
+
+    let mut rows = vec![String::from("   ")];
+    for module in modules {
+        let row = rows.last_mut().expect("starts with a row");
+        if row.len() + module.len() + 2 > MAX_WIDTH {
+            rows.push(format!("    {},", module));
+        } else {
+            row.push_str(&format!(" {},", module));
+        }
+    }
+    format!("use crate::{{\n{}\n}};", rows.join("\n"))
+}
+
+/// Inserts `new_line` among the lines `day_of` recognises so they stay sorted by
+/// day, keeping the indentation of the file; `None` if there are no such lines
+fn insert_in_order(text: &str, day: u32, new_line: &str, day_of: impl Fn(&str) -> Option<u32>) -> Option<String> {
+    let mut lines: Vec<&str> = text.lines().collect();
+    let numbered: Vec<(usize, u32)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(index, line)| Some((index, day_of(line)?)))
+        .collect();
+    let (last, _) = *numbered.last()?;
+    let position = numbered
+        .iter()
+        .find(|&&(_, other)| other > day)
+        .map_or(last + 1, |&(index, _)| index);
+    lines.insert(position, new_line);
+    Some(lines.join("\n") + "\n")
+}
+
+fn module_source(day: u32, title: &str) -> String {
+    format!(
+        r#"//! Advent of Code 2025 - Day {day:02}: {heading}
+
+use crate::answer::Answer;
+use crate::budget::Budget;
+use crate::error::{{ParseError, SolveError}};
+use crate::solution::Solution;
+use crate::utils::Input;
+
+/// Parses the puzzle input, one entry per non-blank line
+pub fn parse(input: &str) -> Result<Vec<String>, ParseError> {{
+    let input = Input::new(input);
+    // e.g. crate::error::parse_number({day}, line_number, line, token)? for each number
+    Ok(input.lines().map(|(_, line)| line.to_string()).collect())
+}}
+
+pub fn part1(_lines: &[String]) -> i64 {{
+    // Your solution here
+    0
+}}
+
+pub fn part2(_lines: &[String]) -> i64 {{
+    // Your solution here
+    0
+}}
+
+pub struct Day{day:02};
+
+impl Solution for Day{day:02} {{
+    type Input = Vec<String>;
+    type Params = ();
+
+    const DAY: u32 = {day};
+    const TITLE: &'static str = "{title}";
+
+    fn parse(input: &str) -> Result<Vec<String>, ParseError> {{
+        parse(input)
+    }}
+
+    fn part1(lines: &Vec<String>, _params: &(), _budget: &Budget) -> Result<Answer, SolveError> {{
+        Ok(part1(lines).into())
+    }}
+
+    fn part2(lines: &Vec<String>, _params: &(), _budget: &Budget) -> Result<Answer, SolveError> {{
+        Ok(part2(lines).into())
+    }}
+}}
+"#,
+        day = day,
+        heading = title,
+        title = title.replace('\\', "\\\\").replace('"', "\\\"")
+    )
+}
+
+/// A manifest with the `test` case and no answers yet, so it is skipped until
+/// they are filled in by hand or by `aoc_rust examples --write`
+fn manifest_stub(day: u32, title: &str) -> String {
+    let case = Case {
+        name: "test".to_string(),
+        source: Source::File(format!("day{:02}_test.txt", day)),
+        parts: None,
+        part1: None,
+        part2: None,
+        params: Vec::new(),
+    };
+    format!(
+        "{}# Fill in the answers from the puzzle text, or run `aoc_rust examples --day {} --write`\n\
+         # part1 = 0\n\
+         # part2 = 0\n",
+        manifest::to_toml(day, title, &[case]),
+        day
+    )
+}
+
+fn problem_stub(day: u32, title: &str) -> String {
+    format!(
+        "# Day {day}: {title}\n\n\
+         <!-- Paste the puzzle description from https://adventofcode.com/2025/day/{day} here -->\n\n\
+         ## Part Two\n\n\
+         <!-- Unlocked after solving part one -->\n"
+    )
+}
+
+fn create_if_missing(path: &Path, contents: &str) -> Result<Change, ScaffoldError> {
+    if path.exists() {
+        return Ok(Change::Kept(path.to_path_buf()));
+    }
+    write(path, contents)?;
+    Ok(Change::Created(path.to_path_buf()))
+}
+
+fn read(path: &Path) -> Result<String, ScaffoldError> {
+    fs::read_to_string(path).map_err(|e| ScaffoldError(format!("cannot read {}: {}", path.display(), e)))
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), ScaffoldError> {
+    fs::write(path, contents).map_err(|e| write_error(path, e))
+}
+
+fn write_error(path: &Path, e: std::io::Error) -> ScaffoldError {
+    ScaffoldError(format!("cannot write {}: {}", path.display(), e))
+}
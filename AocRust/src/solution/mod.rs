@@ -0,0 +1,138 @@
+use std::any::Any;
+use std::fmt;
+
+use crate::answer::Answer;
+use crate::budget::Budget;
+use crate::error::{Error, ParseError, SolveError};
+use crate::params::{self, Params, ParamsError};
+
+/// One of the two puzzles of a day
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+
+    pub fn from_number(number: u8) -> Option<Part> {
+        match number {
+            1 => Some(Part::One),
+            2 => Some(Part::Two),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Part {}", self.number())
+    }
+}
+
+/// A day's puzzle: how to parse its input and how to solve both parts. Its
+/// examples are the cases of its manifest, see [`crate::manifest`].
+pub trait Solution {
+    /// Representation of the puzzle input shared by both parts
+    type Input: 'static;
+    /// Values the puzzle fixes that examples may change; `()` when there are none
+    type Params: Params;
+
+    const DAY: u32;
+    const TITLE: &'static str;
+    /// The final day of the event only has a single puzzle to solve
+    const HAS_PART2: bool = true;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    /// Solvers with long searches spend `budget` as they go and return
+    /// [`SolveError::TimedOut`] once it runs out; quick ones can ignore it
+    fn part1(input: &Self::Input, params: &Self::Params, budget: &Budget) -> Result<Answer, SolveError>;
+
+    /// Search steps a part may take unless `--max-steps` says otherwise, for
+    /// days whose searches could otherwise run forever
+    fn max_steps(_params: &Self::Params) -> Option<u64> {
+        None
+    }
+
+    /// Only called when [`Solution::HAS_PART2`] is set; days without a part 2
+    /// report it as an error rather than panicking
+    fn part2(_input: &Self::Input, _params: &Self::Params, _budget: &Budget) -> Result<Answer, SolveError> {
+        Err(SolveError::Unsolvable(format!("day {} has no part 2", Self::DAY)))
+    }
+}
+
+/// Object-safe view of a [`Solution`] so days can be stored in one registry
+pub trait DynSolution: Sync {
+    fn day(&self) -> u32;
+    fn title(&self) -> &'static str;
+    fn has_part(&self, part: Part) -> bool;
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>, ParseError>;
+
+    /// The day's [`Params`] with `overrides` applied, `&[]` for the puzzle's values
+    fn params(&self, overrides: &[(String, String)]) -> Result<Box<dyn Any>, ParamsError>;
+
+    /// A budget with the `--timeout`/`--max-steps` settings and the step limit
+    /// the day sets for [`DynSolution::params`]
+    fn budget(&self, params: &dyn Any) -> Budget;
+
+    /// Solves a part using the values returned by [`DynSolution::parse`] and
+    /// [`DynSolution::params`]
+    fn solve(&self, part: Part, parsed: &dyn Any, params: &dyn Any, budget: &Budget) -> Result<Answer, SolveError>;
+
+    /// Parses and solves a part in one go, with the puzzle's parameters
+    fn run(&self, part: Part, input: &str, budget: &Budget) -> Result<Answer, Error> {
+        let parsed = self.parse(input)?;
+        let params = self.params(&[]).expect("the puzzle's own parameters are valid");
+        Ok(self.solve(part, parsed.as_ref(), params.as_ref(), budget)?)
+    }
+}
+
+impl<S: Solution + Sync> DynSolution for S {
+    fn day(&self) -> u32 {
+        S::DAY
+    }
+
+    fn title(&self) -> &'static str {
+        S::TITLE
+    }
+
+    fn has_part(&self, part: Part) -> bool {
+        part == Part::One || S::HAS_PART2
+    }
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>, ParseError> {
+        Ok(Box::new(S::parse(input)?))
+    }
+
+    fn params(&self, overrides: &[(String, String)]) -> Result<Box<dyn Any>, ParamsError> {
+        Ok(Box::new(params::with_overrides::<S::Params>(overrides)?))
+    }
+
+    fn budget(&self, params: &dyn Any) -> Budget {
+        let params = params
+            .downcast_ref::<S::Params>()
+            .expect("parameters belong to a different day");
+        Budget::from_settings(S::max_steps(params))
+    }
+
+    fn solve(&self, part: Part, parsed: &dyn Any, params: &dyn Any, budget: &Budget) -> Result<Answer, SolveError> {
+        let input = parsed
+            .downcast_ref::<S::Input>()
+            .expect("parsed input belongs to a different day");
+        let params = params
+            .downcast_ref::<S::Params>()
+            .expect("parameters belong to a different day");
+        match part {
+            Part::One => S::part1(input, params, budget),
+            Part::Two => S::part2(input, params, budget),
+        }
+    }
+}
@@ -0,0 +1,165 @@
+/// Union-find over the elements `0..len`, with path compression and union by
+/// size, so merging and looking up components is close to constant time.
+/// The number of components is kept up to date as sets are merged.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DisjointSet {
+    parent: Vec<usize>,
+    /// Size of the component, only meaningful for roots
+    size: Vec<usize>,
+    components: usize,
+}
+
+impl DisjointSet {
+    /// `len` elements, each in a component of its own
+    pub fn new(len: usize) -> Self {
+        DisjointSet { parent: (0..len).collect(), size: vec![1; len], components: len }
+    }
+
+    /// Number of elements
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Number of components
+    pub fn count(&self) -> usize {
+        self.components
+    }
+
+    /// The representative of `x`'s component, shortening the path to it
+    pub fn find(&mut self, x: usize) -> usize {
+        let root = self.root(x);
+        let mut current = x;
+        while self.parent[current] != root {
+            current = std::mem::replace(&mut self.parent[current], root);
+        }
+        root
+    }
+
+    /// The representative of `x`'s component without changing the structure
+    fn root(&self, mut x: usize) -> usize {
+        while self.parent[x] != x {
+            x = self.parent[x];
+        }
+        x
+    }
+
+    /// Merges the components of `x` and `y`; false if they already were one
+    pub fn union(&mut self, x: usize, y: usize) -> bool {
+        let (mut root_x, mut root_y) = (self.find(x), self.find(y));
+        if root_x == root_y {
+            return false;
+        }
+
+        // Hang the smaller tree below the larger one to keep paths short
+        if self.size[root_x] < self.size[root_y] {
+            std::mem::swap(&mut root_x, &mut root_y);
+        }
+        self.parent[root_y] = root_x;
+        self.size[root_x] += self.size[root_y];
+        self.components -= 1;
+        true
+    }
+
+    /// Whether `x` and `y` are in the same component
+    pub fn connected(&mut self, x: usize, y: usize) -> bool {
+        self.find(x) == self.find(y)
+    }
+
+    /// Number of elements in `x`'s component
+    pub fn size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+
+    /// The size of every component, in no particular order
+    pub fn sizes(&self) -> impl Iterator<Item = usize> {
+        (0..self.len())
+            .filter(|&x| self.parent[x] == x)
+            .map(|root| self.size[root])
+    }
+
+    /// The elements of every component, ordered by their smallest element
+    pub fn components(&self) -> Vec<Vec<usize>> {
+        let mut index_of_root = vec![usize::MAX; self.len()];
+        let mut components: Vec<Vec<usize>> = Vec::with_capacity(self.components);
+        for x in 0..self.len() {
+            let root = self.root(x);
+            if index_of_root[root] == usize::MAX {
+                index_of_root[root] = components.len();
+                components.push(Vec::with_capacity(self.size[root]));
+            }
+            components[index_of_root[root]].push(x);
+        }
+        components
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted_sizes(set: &DisjointSet) -> Vec<usize> {
+        let mut sizes: Vec<usize> = set.sizes().collect();
+        sizes.sort_unstable();
+        sizes
+    }
+
+    #[test]
+    fn starts_with_every_element_alone() {
+        let mut set = DisjointSet::new(4);
+        assert_eq!(set.len(), 4);
+        assert_eq!(set.count(), 4);
+        assert!(!set.connected(0, 1));
+        assert_eq!(set.size(2), 1);
+        assert_eq!(set.components(), [[0], [1], [2], [3]]);
+    }
+
+    #[test]
+    fn union_merges_components_once() {
+        let mut set = DisjointSet::new(5);
+        assert!(set.union(0, 1));
+        assert!(set.union(3, 4));
+        assert!(set.union(1, 4));
+        assert!(!set.union(0, 3));
+        assert!(!set.union(2, 2));
+        assert_eq!(set.count(), 2);
+        assert!(set.connected(0, 3));
+        assert!(!set.connected(0, 2));
+        assert_eq!(set.size(4), 4);
+        assert_eq!(set.size(2), 1);
+    }
+
+    #[test]
+    fn reports_sizes_and_members_of_every_component() {
+        let mut set = DisjointSet::new(7);
+        set.union(5, 1);
+        set.union(1, 3);
+        set.union(6, 4);
+        assert_eq!(sorted_sizes(&set), [1, 1, 2, 3]);
+        assert_eq!(set.sizes().sum::<usize>(), set.len());
+        assert_eq!(set.components(), vec![vec![0], vec![1, 3, 5], vec![2], vec![4, 6]]);
+    }
+
+    #[test]
+    fn find_compresses_paths_without_changing_components() {
+        // Union by size only ever builds shallow trees, so chain the roots by hand
+        let mut set = DisjointSet { parent: vec![0, 0, 1, 2], size: vec![4, 3, 2, 1], components: 1 };
+        assert_eq!(set.find(3), 0);
+        assert_eq!(set.parent, [0, 0, 0, 0]);
+        assert_eq!(set.size(3), 4);
+        assert_eq!(set.components(), [[0, 1, 2, 3]]);
+    }
+
+    #[test]
+    fn handles_the_empty_set() {
+        let set = DisjointSet::new(0);
+        assert!(set.is_empty());
+        assert_eq!(set.count(), 0);
+        assert_eq!(set.sizes().count(), 0);
+        assert!(set.components().is_empty());
+    }
+}
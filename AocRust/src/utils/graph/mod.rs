@@ -0,0 +1,335 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Dense ID of a node, its index in the order nodes were first named
+pub type NodeId = u32;
+
+/// Collects named nodes and directed edges, then freezes them into a [`Graph`]
+#[derive(Debug, Clone, Default)]
+pub struct GraphBuilder {
+    names: Names,
+    edges: Vec<(NodeId, NodeId)>,
+}
+
+/// Node names and the IDs they were interned as
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Names {
+    ids: HashMap<String, NodeId>,
+    names: Vec<String>,
+}
+
+impl GraphBuilder {
+    pub fn new() -> Self {
+        GraphBuilder::default()
+    }
+
+    /// The ID of the node called `name`, adding the node the first time it is seen
+    pub fn node(&mut self, name: &str) -> NodeId {
+        if let Some(&id) = self.names.ids.get(name) {
+            return id;
+        }
+        let id = self.names.names.len() as NodeId;
+        self.names.ids.insert(name.to_string(), id);
+        self.names.names.push(name.to_string());
+        id
+    }
+
+    /// Adds an edge from `from` to `to`
+    pub fn edge(&mut self, from: NodeId, to: NodeId) {
+        self.edges.push((from, to));
+    }
+
+    pub fn build(self) -> Graph {
+        Graph::from_edges(Arc::new(self.names), &self.edges)
+    }
+}
+
+/// A directed graph over interned node names, stored in compressed sparse row
+/// form: the successors of node `n` are `targets[offsets[n]..offsets[n + 1]]`,
+/// in the order their edges were added. Graphs derived from one another
+/// share their names.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Graph {
+    names: Arc<Names>,
+    offsets: Vec<usize>,
+    targets: Vec<NodeId>,
+}
+
+impl Graph {
+    fn from_edges(names: Arc<Names>, edges: &[(NodeId, NodeId)]) -> Self {
+        let len = names.names.len();
+        // Counting sort by source keeps each node's edges in insertion order
+        let mut offsets = vec![0; len + 1];
+        for &(from, _) in edges {
+            offsets[from as usize + 1] += 1;
+        }
+        for n in 0..len {
+            offsets[n + 1] += offsets[n];
+        }
+        let mut next = offsets.clone();
+        let mut targets = vec![0; edges.len()];
+        for &(from, to) in edges {
+            targets[next[from as usize]] = to;
+            next[from as usize] += 1;
+        }
+        Graph { names, offsets, targets }
+    }
+
+    /// Number of nodes
+    pub fn len(&self) -> usize {
+        self.offsets.len().saturating_sub(1)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of edges
+    pub fn edge_count(&self) -> usize {
+        self.targets.len()
+    }
+
+    /// The ID of the node called `name`, if there is one
+    pub fn id(&self, name: &str) -> Option<NodeId> {
+        self.names.ids.get(name).copied()
+    }
+
+    pub fn name(&self, id: NodeId) -> &str {
+        &self.names.names[id as usize]
+    }
+
+    /// Every node ID, in the order the nodes were first named
+    pub fn nodes(&self) -> impl Iterator<Item = NodeId> + use<> {
+        0..self.len() as NodeId
+    }
+
+    /// The nodes `id` has an edge to
+    pub fn successors(&self, id: NodeId) -> &[NodeId] {
+        &self.targets[self.offsets[id as usize]..self.offsets[id as usize + 1]]
+    }
+
+    /// The same nodes with every edge pointing the other way
+    pub fn reversed(&self) -> Graph {
+        let edges: Vec<(NodeId, NodeId)> = self
+            .nodes()
+            .flat_map(|from| self.successors(from).iter().map(move |&to| (to, from)))
+            .collect();
+        Graph::from_edges(Arc::clone(&self.names), &edges)
+    }
+
+    /// The same nodes with only the edges between nodes that are kept
+    pub fn subgraph(&self, keep: &[bool]) -> Graph {
+        let edges: Vec<(NodeId, NodeId)> = self
+            .nodes()
+            .filter(|&from| keep[from as usize])
+            .flat_map(|from| self.successors(from).iter().map(move |&to| (from, to)))
+            .filter(|&(_, to)| keep[to as usize])
+            .collect();
+        Graph::from_edges(Arc::clone(&self.names), &edges)
+    }
+
+    /// Which nodes can be reached from `start`, `start` included, indexed by ID
+    pub fn reachable_from(&self, start: NodeId) -> Vec<bool> {
+        let mut seen = vec![false; self.len()];
+        let mut stack = vec![start];
+        seen[start as usize] = true;
+        while let Some(node) = stack.pop() {
+            for &next in self.successors(node) {
+                if !seen[next as usize] {
+                    seen[next as usize] = true;
+                    stack.push(next);
+                }
+            }
+        }
+        seen
+    }
+
+    /// Every node ordered so that each edge points forward, or, when there is no
+    /// such order, a cycle as the nodes along it with the first repeated at the end
+    pub fn topological_order(&self) -> Result<Vec<NodeId>, Vec<NodeId>> {
+        let mut incoming = vec![0usize; self.len()];
+        for &to in &self.targets {
+            incoming[to as usize] += 1;
+        }
+
+        // Kahn's algorithm: repeatedly take a node nothing points to any more
+        let mut ready: Vec<NodeId> = self.nodes().filter(|&n| incoming[n as usize] == 0).collect();
+        let mut order = Vec::with_capacity(self.len());
+        while let Some(node) = ready.pop() {
+            order.push(node);
+            for &next in self.successors(node) {
+                incoming[next as usize] -= 1;
+                if incoming[next as usize] == 0 {
+                    ready.push(next);
+                }
+            }
+        }
+
+        if order.len() == self.len() {
+            Ok(order)
+        } else {
+            Err(self.find_cycle().expect("nodes left over by Kahn's algorithm lie on a cycle"))
+        }
+    }
+
+    /// A cycle as the nodes along it, first node repeated at the end, if there is one
+    pub fn find_cycle(&self) -> Option<Vec<NodeId>> {
+        const UNVISITED: u8 = 0;
+        const ON_PATH: u8 = 1;
+        const DONE: u8 = 2;
+
+        let mut state = vec![UNVISITED; self.len()];
+        for root in self.nodes() {
+            if state[root as usize] != UNVISITED {
+                continue;
+            }
+
+            // Iterative DFS; each frame is a node and how many successors it has tried
+            let mut path: Vec<(NodeId, usize)> = vec![(root, 0)];
+            state[root as usize] = ON_PATH;
+            while let Some(frame) = path.last_mut() {
+                let node = frame.0;
+                let Some(&next) = self.successors(node).get(frame.1) else {
+                    state[node as usize] = DONE;
+                    path.pop();
+                    continue;
+                };
+                frame.1 += 1;
+
+                match state[next as usize] {
+                    UNVISITED => {
+                        state[next as usize] = ON_PATH;
+                        path.push((next, 0));
+                    }
+                    ON_PATH => {
+                        let start = path.iter().position(|&(n, _)| n == next).expect("node is on the path");
+                        let mut cycle: Vec<NodeId> = path[start..].iter().map(|&(n, _)| n).collect();
+                        cycle.push(next);
+                        return Some(cycle);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A graph with an edge for every `from -> to` pair, naming nodes in the order they appear
+    fn graph(edges: &[(&str, &str)]) -> Graph {
+        let mut builder = GraphBuilder::new();
+        for &(from, to) in edges {
+            let from = builder.node(from);
+            let to = builder.node(to);
+            builder.edge(from, to);
+        }
+        builder.build()
+    }
+
+    fn names(graph: &Graph, ids: &[NodeId]) -> Vec<String> {
+        ids.iter().map(|&id| graph.name(id).to_string()).collect()
+    }
+
+    fn position(order: &[NodeId], id: NodeId) -> usize {
+        order.iter().position(|&n| n == id).unwrap()
+    }
+
+    #[test]
+    fn interns_names_in_first_seen_order() {
+        let mut builder = GraphBuilder::new();
+        assert_eq!(builder.node("you"), 0);
+        assert_eq!(builder.node("out"), 1);
+        assert_eq!(builder.node("you"), 0);
+        let graph = builder.build();
+        assert_eq!(graph.len(), 2);
+        assert_eq!(graph.id("out"), Some(1));
+        assert_eq!(graph.id("svr"), None);
+        assert_eq!(graph.name(1), "out");
+    }
+
+    #[test]
+    fn keeps_successors_in_insertion_order() {
+        let g = graph(&[("a", "c"), ("b", "c"), ("a", "b"), ("a", "a")]);
+        let (a, b, c) = (g.id("a").unwrap(), g.id("b").unwrap(), g.id("c").unwrap());
+        assert_eq!(g.successors(a), &[c, b, a]);
+        assert_eq!(g.successors(b), &[c]);
+        assert!(g.successors(c).is_empty());
+        assert_eq!(g.edge_count(), 4);
+    }
+
+    #[test]
+    fn orders_a_dag_so_every_edge_points_forward() {
+        let g = graph(&[("a", "b"), ("a", "c"), ("b", "d"), ("c", "d"), ("d", "e"), ("a", "e")]);
+        let order = g.topological_order().unwrap();
+        assert_eq!(order.len(), g.len());
+        for from in g.nodes() {
+            for &to in g.successors(from) {
+                assert!(position(&order, from) < position(&order, to), "{} -> {}", g.name(from), g.name(to));
+            }
+        }
+        assert_eq!(g.find_cycle(), None);
+    }
+
+    #[test]
+    fn returns_the_cycle_when_there_is_no_order() {
+        let g = graph(&[("start", "a"), ("a", "b"), ("b", "c"), ("c", "a"), ("c", "end")]);
+        let cycle = g.topological_order().unwrap_err();
+        assert_eq!(names(&g, &cycle), ["a", "b", "c", "a"]);
+        assert_eq!(g.find_cycle(), Some(cycle));
+    }
+
+    #[test]
+    fn reports_a_self_loop_as_a_cycle() {
+        let g = graph(&[("a", "x"), ("x", "x")]);
+        let cycle = g.topological_order().unwrap_err();
+        assert_eq!(names(&g, &cycle), ["x", "x"]);
+    }
+
+    #[test]
+    fn handles_the_empty_graph() {
+        let g = GraphBuilder::new().build();
+        assert!(g.is_empty());
+        assert_eq!(g.edge_count(), 0);
+        assert_eq!(g.nodes().count(), 0);
+        assert_eq!(g.topological_order(), Ok(Vec::new()));
+        assert_eq!(g.find_cycle(), None);
+        assert!(g.reversed().is_empty());
+        assert!(g.subgraph(&[]).is_empty());
+    }
+
+    #[test]
+    fn reverses_every_edge() {
+        let g = graph(&[("a", "b"), ("a", "c"), ("b", "c")]);
+        let reversed = g.reversed();
+        let (a, b, c) = (g.id("a").unwrap(), g.id("b").unwrap(), g.id("c").unwrap());
+        assert!(reversed.successors(a).is_empty());
+        assert_eq!(reversed.successors(b), &[a]);
+        assert_eq!(reversed.successors(c), &[a, b]);
+        assert_eq!(reversed.id("c"), Some(c));
+        assert_eq!(reversed.reversed(), g);
+    }
+
+    #[test]
+    fn subgraph_keeps_only_edges_between_kept_nodes() {
+        let g = graph(&[("a", "b"), ("b", "c"), ("a", "c"), ("c", "d")]);
+        let (a, b, c) = (g.id("a").unwrap(), g.id("b").unwrap(), g.id("c").unwrap());
+        let sub = g.subgraph(&[true, false, true, true]);
+        assert_eq!(sub.len(), g.len());
+        assert_eq!(sub.successors(a), &[c]);
+        assert!(sub.successors(b).is_empty());
+        assert_eq!(sub.edge_count(), 2);
+    }
+
+    #[test]
+    fn reaches_nodes_along_edges_only() {
+        let g = graph(&[("a", "b"), ("b", "c"), ("d", "a"), ("c", "a")]);
+        assert_eq!(g.reachable_from(g.id("a").unwrap()), [true, true, true, false]);
+        assert_eq!(g.reachable_from(g.id("d").unwrap()), [true, true, true, true]);
+        assert_eq!(g.reversed().reachable_from(g.id("a").unwrap()), [true, true, true, true]);
+        assert_eq!(g.reachable_from(g.id("c").unwrap()), [true, true, true, false]);
+    }
+}
@@ -0,0 +1,251 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+/// Row and column offsets of the 4 orthogonal neighbours: N, E, S, W
+pub const ORTHOGONAL: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+/// Row and column offsets of all 8 neighbours: N, NE, E, SE, S, SW, W, NW
+pub const ALL_DIRECTIONS: [(isize, isize); 8] =
+    [(-1, 0), (-1, 1), (0, 1), (1, 1), (1, 0), (1, -1), (0, -1), (-1, -1)];
+
+/// A rectangular grid stored row by row in one `Vec`.
+///
+/// Cells are addressed as `(row, col)`, either through [`Grid::get`], which
+/// returns `None` outside the grid, or by indexing with `grid[(row, col)]`,
+/// which panics there like slice indexing does.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// A `width` by `height` grid with every cell set to `value`
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid { cells: vec![value; width * height], width, height }
+    }
+
+    /// Builds a grid from its rows, or `None` if they are not all the same width
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Self> {
+        let width = rows.first().map_or(0, Vec::len);
+        if rows.iter().any(|row| row.len() != width) {
+            return None;
+        }
+        let height = if width == 0 { 0 } else { rows.len() };
+        Some(Grid { cells: rows.into_iter().flatten().collect(), width, height })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The cell at `(row, col)`, `None` outside the grid
+    pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+        if row < self.height && col < self.width { Some(&self.cells[row * self.width + col]) } else { None }
+    }
+
+    pub fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut T> {
+        if row < self.height && col < self.width { Some(&mut self.cells[row * self.width + col]) } else { None }
+    }
+
+    /// The position `(row, col)` moved by `offset`, if it is still inside the grid
+    pub fn step(&self, (row, col): (usize, usize), (dr, dc): (isize, isize)) -> Option<(usize, usize)> {
+        let row = row.checked_add_signed(dr).filter(|&row| row < self.height)?;
+        let col = col.checked_add_signed(dc).filter(|&col| col < self.width)?;
+        Some((row, col))
+    }
+
+    /// Positions of the orthogonal neighbours of `(row, col)` that are inside the grid
+    pub fn neighbours4(&self, row: usize, col: usize) -> impl Iterator<Item = (usize, usize)> {
+        ORTHOGONAL.iter().filter_map(move |&offset| self.step((row, col), offset))
+    }
+
+    /// Positions of all neighbours of `(row, col)`, diagonals included, that are inside the grid
+    pub fn neighbours8(&self, row: usize, col: usize) -> impl Iterator<Item = (usize, usize)> {
+        ALL_DIRECTIONS.iter().filter_map(move |&offset| self.step((row, col), offset))
+    }
+
+    /// Every position, row by row
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| (row, col)))
+    }
+
+    /// Every cell with its position, row by row
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    /// The first position, row by row, holding `value`
+    pub fn find(&self, value: &T) -> Option<(usize, usize)>
+    where
+        T: PartialEq,
+    {
+        let index = self.cells.iter().position(|cell| cell == value)?;
+        Some((index / self.width, index % self.width))
+    }
+
+    /// One row as a slice
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on a zero width, which only an empty grid has
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// One column, top to bottom
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        assert!(col < self.width, "column {} outside a grid {} wide", col, self.width);
+        self.cells.iter().skip(col).step_by(self.width)
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        assert!(col < self.width, "column {} outside a grid {} wide", col, self.width);
+        &self.cells[row * self.width + col]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+        assert!(col < self.width, "column {} outside a grid {} wide", col, self.width);
+        &mut self.cells[row * self.width + col]
+    }
+}
+
+/// How a cell is drawn when a grid is printed
+pub trait Cell {
+    fn to_char(&self) -> char;
+}
+
+impl Cell for u8 {
+    fn to_char(&self) -> char {
+        *self as char
+    }
+}
+
+impl Cell for char {
+    fn to_char(&self) -> char {
+        *self
+    }
+}
+
+/// Filled cells as `#`, empty ones as `.`, like the puzzle diagrams
+impl Cell for bool {
+    fn to_char(&self) -> char {
+        if *self { '#' } else { '.' }
+    }
+}
+
+/// Renders the grid one row per line, e.g. to print a map while debugging
+impl<T: Cell> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, row) in self.rows().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell.to_char())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map(text: &str) -> Grid<u8> {
+        Grid::from_rows(text.lines().map(|line| line.as_bytes().to_vec()).collect()).unwrap()
+    }
+
+    #[test]
+    fn builds_from_rows_of_equal_width() {
+        let grid = map("abc\ndef");
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(1, 2)], b'f');
+        assert_eq!(grid.row(1), b"def");
+        assert_eq!(grid.rows().collect::<Vec<_>>(), [b"abc", b"def"]);
+        assert!(Grid::from_rows(vec![vec![1, 2], vec![3]]).is_none());
+    }
+
+    #[test]
+    fn get_is_none_outside_the_grid() {
+        let mut grid = Grid::new(3, 2, 0);
+        assert_eq!(grid.get(1, 2), Some(&0));
+        assert_eq!(grid.get(2, 0), None);
+        assert_eq!(grid.get(0, 3), None);
+        *grid.get_mut(1, 0).unwrap() = 7;
+        grid[(0, 2)] = 5;
+        assert_eq!(grid.row(0), [0, 0, 5]);
+        assert_eq!(grid.row(1), [7, 0, 0]);
+        assert!(grid.get_mut(0, 3).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "column 3 outside a grid 3 wide")]
+    fn indexing_past_the_last_column_panics_instead_of_wrapping() {
+        let grid = map("abc\ndef");
+        let _ = grid[(0, 3)];
+    }
+
+    #[test]
+    fn neighbours_stay_inside_the_grid() {
+        let grid = Grid::new(3, 3, ());
+        assert_eq!(grid.neighbours4(0, 0).collect::<Vec<_>>(), [(0, 1), (1, 0)]);
+        assert_eq!(grid.neighbours4(1, 1).count(), 4);
+        assert_eq!(grid.neighbours8(0, 0).collect::<Vec<_>>(), [(0, 1), (1, 1), (1, 0)]);
+        assert_eq!(grid.neighbours8(1, 1).count(), 8);
+        assert_eq!(grid.neighbours8(2, 2).collect::<Vec<_>>(), [(1, 2), (2, 1), (1, 1)]);
+        assert_eq!(grid.step((2, 0), (0, -1)), None);
+        assert_eq!(grid.step((2, 0), (-2, 2)), Some((0, 2)));
+    }
+
+    #[test]
+    fn walks_positions_columns_and_cells_in_order() {
+        let grid = map("ab\ncd\nef");
+        assert_eq!(grid.positions().collect::<Vec<_>>(), [(0, 0), (0, 1), (1, 0), (1, 1), (2, 0), (2, 1)]);
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), b"bdf");
+        assert_eq!(grid.iter().nth(3), Some(((1, 1), &b'd')));
+        assert_eq!(grid.find(&b'e'), Some((2, 0)));
+        assert_eq!(grid.find(&b'z'), None);
+    }
+
+    #[test]
+    fn displays_one_row_per_line() {
+        assert_eq!(map("#.#\n.#.").to_string(), "#.#\n.#.");
+        let mut filled = Grid::new(2, 2, false);
+        filled[(1, 0)] = true;
+        assert_eq!(filled.to_string(), "..\n#.");
+    }
+
+    #[test]
+    fn handles_the_empty_grid() {
+        let grid: Grid<u8> = Grid::from_rows(Vec::new()).unwrap();
+        assert!(grid.is_empty());
+        assert_eq!((grid.width(), grid.height()), (0, 0));
+        assert_eq!(grid.positions().count(), 0);
+        assert_eq!(grid.rows().count(), 0);
+        assert_eq!(grid.find(&b'#'), None);
+        assert_eq!(grid.to_string(), "");
+        assert!(Grid::<u8>::from_rows(vec![Vec::new(), Vec::new()]).unwrap().is_empty());
+    }
+}
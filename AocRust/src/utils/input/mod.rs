@@ -0,0 +1,157 @@
+use std::borrow::Cow;
+
+use super::grid::Grid;
+
+/// Puzzle text with its line endings normalized to `\n`, so days never have
+/// to care whether a file was saved with `\r\n`. Leading and trailing spaces
+/// are kept, since some inputs (e.g. day 06) are aligned by column.
+///
+/// Lines are handed out with their 1-based line number in the original text,
+/// ready for [`ParseError`](crate::error::ParseError) positions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Input<'a> {
+    text: Cow<'a, str>,
+}
+
+impl<'a> Input<'a> {
+    /// Normalizes `\r\n` and lone `\r` line endings, borrowing when there are none
+    pub fn new(text: &'a str) -> Self {
+        let text = if text.contains('\r') {
+            Cow::Owned(text.replace("\r\n", "\n").replace('\r', "\n"))
+        } else {
+            Cow::Borrowed(text)
+        };
+        Input { text }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// Whether the input holds nothing but whitespace
+    pub fn is_blank(&self) -> bool {
+        self.text.trim().is_empty()
+    }
+
+    /// The non-blank lines, each with its line number
+    pub fn lines(&self) -> impl Iterator<Item = (usize, &str)> {
+        self.text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| (index + 1, line))
+    }
+
+    /// Groups of consecutive non-blank lines, split at blank lines
+    pub fn sections(&self) -> Vec<Vec<(usize, &str)>> {
+        let mut sections: Vec<Vec<(usize, &str)>> = Vec::new();
+        let mut previous = 0;
+        for (number, line) in self.lines() {
+            match sections.last_mut() {
+                Some(section) if number == previous + 1 => section.push((number, line)),
+                _ => sections.push(vec![(number, line)]),
+            }
+            previous = number;
+        }
+        sections
+    }
+
+    /// The non-blank lines as rows of bytes, padded with spaces to the widest row
+    pub fn grid(&self) -> Grid<u8> {
+        let width = self.lines().map(|(_, line)| line.len()).max().unwrap_or(0);
+        let rows = self
+            .lines()
+            .map(|(_, line)| {
+                let mut row = line.as_bytes().to_vec();
+                row.resize(width, b' ');
+                row
+            })
+            .collect();
+        Grid::from_rows(rows).expect("rows are padded to the same width")
+    }
+
+    /// Every integer in the text, in order. A `-` is a sign only when it does
+    /// not follow a digit, so ranges like `3-5` read as 3 and 5; numbers too
+    /// large for an `i64` are skipped.
+    pub fn ints(&self) -> Vec<i64> {
+        ints(&self.text)
+    }
+}
+
+/// Every integer in `text`, as [`Input::ints`] reads them; handy for a single line
+pub fn ints(text: &str) -> Vec<i64> {
+    let bytes = text.as_bytes();
+    let mut numbers = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let negative = bytes[i] == b'-'
+            && bytes.get(i + 1).is_some_and(u8::is_ascii_digit)
+            && (i == 0 || !bytes[i - 1].is_ascii_digit());
+        if !negative && !bytes[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        i += 1;
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        if let Ok(number) = text[start..i].parse() {
+            numbers.push(number);
+        }
+    }
+
+    numbers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_line_endings_and_skips_blank_lines() {
+        let input = Input::new("a\r\n\r\n  b\rc\n");
+        assert_eq!(input.as_str(), "a\n\n  b\nc\n");
+        assert_eq!(input.lines().collect::<Vec<_>>(), [(1, "a"), (3, "  b"), (4, "c")]);
+        assert!(matches!(Input::new("a\nb").text, Cow::Borrowed(_)));
+        assert!(Input::new(" \n\t\n").is_blank());
+    }
+
+    #[test]
+    fn splits_sections_at_blank_lines() {
+        let input = Input::new("1\n2\n\n\n3\n\n4\n5\n");
+        let sections = input.sections();
+        assert_eq!(sections, [vec![(1, "1"), (2, "2")], vec![(5, "3")], vec![(7, "4"), (8, "5")]]);
+    }
+
+    #[test]
+    fn pads_the_grid_to_the_widest_row() {
+        let grid = Input::new("ab\n\nc\nde f\n").grid();
+        assert_eq!((grid.width(), grid.height()), (4, 3));
+        assert_eq!(grid.to_string(), "ab  \nc   \nde f");
+        assert!(Input::new("").grid().is_empty());
+    }
+
+    #[test]
+    fn reads_every_integer_in_order() {
+        assert_eq!(ints("162,817,812"), [162, 817, 812]);
+        assert_eq!(ints("x=-3, y=+4: 007"), [-3, 4, 7]);
+        assert_eq!(Input::new("1 2\r\n-3\n").ints(), [1, 2, -3]);
+        assert!(ints("no numbers - here").is_empty());
+    }
+
+    #[test]
+    fn reads_a_dash_after_a_digit_as_a_range() {
+        assert_eq!(ints("3-5,10-14"), [3, 5, 10, 14]);
+        assert_eq!(ints("3--5"), [3, -5]);
+        assert_eq!(ints("-9223372036854775808"), [i64::MIN]);
+    }
+
+    #[test]
+    fn skips_numbers_too_large_for_an_i64() {
+        assert_eq!(ints("1 99999999999999999999 2"), [1, 2]);
+        assert_eq!(ints("9223372036854775808,-9223372036854775809"), Vec::<i64>::new());
+    }
+}
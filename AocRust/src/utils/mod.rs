@@ -1,95 +1,151 @@
 use std::fs;
+use std::io::{self, Read};
 use std::path::Path;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
-/// Reads the entire content of a file as a single string
-pub fn read_input(file_path: &str) -> Result<String, std::io::Error> {
-    if !Path::new(file_path).exists() {
-        return Err(std::io::Error::new(
-            std::io::ErrorKind::NotFound,
-            format!("Input file not found: {}", file_path),
-        ));
-    }
-    
-    let content = fs::read_to_string(file_path)?;
-    Ok(content.trim().to_string())
+use crate::answer::Answer;
+use crate::error::Error;
+use crate::memory;
+use crate::report::{Outcome, Status};
+
+pub mod disjoint_set;
+pub mod graph;
+pub mod grid;
+pub mod input;
+
+pub use disjoint_set::DisjointSet;
+pub use input::Input;
+
+/// Path that stands for standard input
+pub const STDIN_PATH: &str = "-";
+
+/// Reads the entire content of a file as a single string; `-` reads standard input.
+/// The text is returned as is: [`Input`] takes care of line endings and blank lines.
+pub fn read_input(file_path: impl AsRef<Path>) -> Result<String, io::Error> {
+    let file_path = file_path.as_ref();
+    let content = if file_path.as_os_str() == STDIN_PATH {
+        let mut content = String::new();
+        io::stdin().read_to_string(&mut content)?;
+        content
+    } else {
+        fs::read_to_string(file_path).map_err(|e| match e.kind() {
+            io::ErrorKind::NotFound => io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("Input file not found: {}", file_path.display()),
+            ),
+            _ => io::Error::new(e.kind(), format!("{}: {}", file_path.display(), e)),
+        })?
+    };
+
+    Ok(content)
 }
 
 /// Reads a file and returns a vector of non-empty lines
-#[allow(dead_code)]
-pub fn read_lines(file_path: &str) -> Result<Vec<String>, std::io::Error> {
+pub fn read_lines(file_path: impl AsRef<Path>) -> Result<Vec<String>, io::Error> {
     let content = read_input(file_path)?;
-    Ok(content
-        .lines()
-        .filter(|line| !line.trim().is_empty())
-        .map(|line| line.to_string())
-        .collect())
+    Ok(Input::new(&content).lines().map(|(_, line)| line.to_string()).collect())
 }
 
-/// Runs a test case and compares the result with expected value
-pub fn run_test<T: std::fmt::Display + PartialEq>(
-    test_name: &str,
-    test_func: impl FnOnce() -> T,
-    expected: T,
-) -> bool {
-    print!("Running {}... ", test_name);
-    
+/// Runs a solver and times it, comparing its answer with `expected` when given.
+/// Nothing is printed, so callers can choose how to report the [`Outcome`].
+pub fn check<T, E>(solver: impl FnOnce() -> Result<T, E>, expected: Option<Answer>) -> Outcome
+where
+    T: Into<Answer>,
+    E: Into<Error>,
+{
     let start = Instant::now();
-    let result = test_func();
+    let (result, memory) = memory::measure(|| solver().map(Into::into));
     let elapsed = start.elapsed();
-    
-    let passed = result == expected;
-    
-    if passed {
-        println!("✓ PASSED (Result: {}) [{:.3}s]", result, elapsed.as_secs_f64());
-    } else {
-        println!("✗ FAILED (Expected: {}, Got: {}) [{:.3}s]", expected, result, elapsed.as_secs_f64());
-    }
-    
-    passed
+
+    Outcome { memory, ..Outcome::new(result, expected, elapsed) }
 }
 
-/// Runs a solution part with both test and real inputs
-pub fn run_solution<T: std::fmt::Display>(
-    part_name: &str,
-    solver: impl Fn(&str) -> T,
-    test_input_path: &str,
-    real_input_path: &str,
-    expected_test_result: Option<T>,
-) where
-    T: PartialEq,
+/// Runs a test case and compares the result with expected value as an [`Answer`],
+/// so any integer width or a string can be checked. A test case that returns an
+/// error counts as failed.
+pub fn run_test<T, E>(
+    test_name: &str,
+    test_func: impl FnOnce() -> Result<T, E>,
+    expected: impl Into<Answer>,
+) -> Outcome
+where
+    T: Into<Answer>,
+    E: Into<Error>,
 {
-    println!("\n=== {} ===", part_name);
+    print!("Running {}... ", test_name);
+    
+    let outcome = check(test_func, Some(expected.into()));
+    let elapsed = format_cost(&outcome);
     
-    // Run test if expected result is provided
-    if let Some(expected) = expected_test_result {
-        if Path::new(test_input_path).exists() {
-            if let Ok(test_input) = read_input(test_input_path) {
-                run_test(
-                    &format!("{} (Test)", part_name),
-                    || solver(&test_input),
-                    expected,
-                );
-            }
+    match (&outcome.answer, &outcome.expected, &outcome.error) {
+        (Some(result), _, _) if outcome.status == Status::Pass => {
+            println!("✓ PASSED (Result: {}) [{}]", result, elapsed);
+        }
+        (Some(result), Some(expected), _) => {
+            println!("✗ FAILED (Expected: {}, Got: {}) [{}]", expected, result, elapsed);
+        }
+        (_, _, error) if outcome.status == Status::TimedOut => {
+            println!("⏱ TIMED OUT ({}) [{}]", timeout_reason(error), elapsed);
+        }
+        (_, _, error) => {
+            println!("✗ ERROR ({}) [{}]", error.as_deref().unwrap_or_default(), elapsed);
         }
     }
     
-    // Run with real input
-    if Path::new(real_input_path).exists() {
-        match read_input(real_input_path) {
-            Ok(real_input) => {
-                print!("Running {} (Real Input)... ", part_name);
-                let start = Instant::now();
-                let result = solver(&real_input);
-                let elapsed = start.elapsed();
-                println!("Result: {} [{:.3}s]", result, elapsed.as_secs_f64());
-            }
-            Err(e) => {
-                println!("ERROR: {}", e);
-            }
+    outcome
+}
+
+/// Finishes a "Running ... (Real Input)... " line with the answer or error
+pub fn print_real_outcome(outcome: &Outcome) {
+    let elapsed = format_cost(outcome);
+    match (&outcome.answer, &outcome.error) {
+        (Some(answer), _) => println!("Result: {} [{}]", answer, elapsed),
+        (None, error) if outcome.status == Status::TimedOut => {
+            println!("TIMED OUT: {} [{}]", timeout_reason(error), elapsed)
         }
+        (None, error) => println!("ERROR: {} [{}]", error.as_deref().unwrap_or_default(), elapsed),
+    }
+}
+
+/// A timeout's error message without the "timed out" the status already says
+fn timeout_reason(error: &Option<String>) -> &str {
+    let error = error.as_deref().unwrap_or_default();
+    error.strip_prefix("timed out: ").unwrap_or(error)
+}
+
+/// An outcome's duration, followed by its allocations when they were counted
+pub fn format_cost(outcome: &Outcome) -> String {
+    match &outcome.memory {
+        Some(memory) => format!("{}, {}", format_duration(outcome.duration), memory),
+        None => format_duration(outcome.duration),
+    }
+}
+
+/// Formats a duration with a unit suited to its size (ns, µs, ms or s)
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos();
+    if nanos < 1_000 {
+        format!("{}ns", nanos)
+    } else if nanos < 1_000_000 {
+        format!("{:.2}µs", nanos as f64 / 1e3)
+    } else if nanos < 1_000_000_000 {
+        format!("{:.2}ms", nanos as f64 / 1e6)
+    } else {
+        format!("{:.3}s", nanos as f64 / 1e9)
+    }
+}
+
+/// Formats a byte count with a binary unit (B, KiB, MiB or GiB)
+pub fn format_bytes(bytes: u64) -> String {
+    const KIB: f64 = 1024.0;
+    let value = bytes as f64;
+    if value < KIB {
+        format!("{}B", bytes)
+    } else if value < KIB * KIB {
+        format!("{:.1}KiB", value / KIB)
+    } else if value < KIB * KIB * KIB {
+        format!("{:.1}MiB", value / (KIB * KIB))
     } else {
-        println!("⚠ Real input file not found: {}", real_input_path);
-        println!("  Please download your puzzle input from https://adventofcode.com/2025/day/X/input");
+        format!("{:.2}GiB", value / (KIB * KIB * KIB))
     }
 }
@@ -0,0 +1,218 @@
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use crate::answer::Answer;
+use crate::error::Error;
+use crate::inputs::Inputs;
+use crate::memory::{self, AllocStats};
+use crate::report::{InputKind, Outcome, Record};
+use crate::solution::{DynSolution, Part};
+use crate::utils;
+
+pub use crate::report::Status;
+
+/// Text before each accepted answer in a problem description
+pub const ANSWER_PREFIX: &str = "Your puzzle answer was ";
+/// Heading that starts the part 2 section of a problem description
+pub const PART_TWO_HEADING: &str = "## Part Two";
+
+/// Accepted answers recorded in a problem description
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RecordedAnswers {
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+}
+
+impl RecordedAnswers {
+    pub fn get(&self, part: Part) -> Option<&Answer> {
+        match part {
+            Part::One => self.part1.as_ref(),
+            Part::Two => self.part2.as_ref(),
+        }
+    }
+}
+
+/// Extracts the "**Your puzzle answer was X.**" lines from a problem description.
+/// Answers above the "## Part Two" heading belong to part 1, the rest to part 2.
+pub fn recorded_answers(markdown: &str) -> RecordedAnswers {
+    let (part1_text, part2_text) = match markdown.find(PART_TWO_HEADING) {
+        Some(index) => markdown.split_at(index),
+        None => (markdown, ""),
+    };
+
+    RecordedAnswers {
+        part1: find_answer(part1_text),
+        part2: find_answer(part2_text),
+    }
+}
+
+fn find_answer(text: &str) -> Option<Answer> {
+    let start = text.find(ANSWER_PREFIX)? + ANSWER_PREFIX.len();
+    let rest = &text[start..];
+    let end = rest.find(['.', '*', '\n']).unwrap_or(rest.len());
+    let answer = rest[..end].trim();
+
+    if answer.is_empty() { None } else { answer.parse().ok() }
+}
+
+/// Outcome of checking one part against its recorded answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Verification {
+    pub day: u32,
+    pub part: Part,
+    pub expected: Option<Answer>,
+    pub actual: Option<Result<Answer, Error>>,
+    pub status: Status,
+    /// Time spent solving the part, zero when it was not run
+    pub duration: Duration,
+    /// What solving the part allocated, with the `alloc-stats` feature
+    pub memory: Option<AllocStats>,
+    /// Where the recorded answer and the real input were looked up
+    pub problem_path: PathBuf,
+    pub input_path: PathBuf,
+}
+
+impl fmt::Display for Verification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Day {:02} {}: {:<7}", self.day, self.part, self.status)?;
+        match (&self.expected, &self.actual) {
+            (_, Some(Err(e))) => write!(f, " ({})", e),
+            (Some(expected), Some(Ok(actual))) if self.status == Status::Fail => {
+                write!(f, " (Expected: {}, Got: {})", expected, actual)
+            }
+            (_, Some(Ok(actual))) if self.status == Status::Pass => write!(f, " ({})", actual),
+            (None, _) => write!(f, " (no recorded answer in {})", self.problem_path.display()),
+            (Some(_), _) => write!(f, " (real input not found: {})", self.input_path.display()),
+        }
+    }
+}
+
+impl Verification {
+    /// The verification as a report record for the real input
+    pub fn to_record(&self) -> Record {
+        let (answer, error) = match &self.actual {
+            Some(Ok(answer)) => (Some(answer.clone()), None),
+            Some(Err(e)) => (None, Some(e.to_string())),
+            None => (None, None),
+        };
+        Record {
+            day: self.day,
+            part: self.part,
+            input: InputKind::Real,
+            path: self.input_path.display().to_string(),
+            outcome: Outcome {
+                answer,
+                expected: self.expected.clone(),
+                error,
+                duration: self.duration,
+                status: self.status,
+                memory: self.memory,
+            },
+        }
+    }
+}
+
+/// Solves a day's real input and compares each part with the recorded answers
+pub fn verify_day(solution: &dyn DynSolution, inputs: &Inputs) -> Vec<Verification> {
+    let day = solution.day();
+    let problem_path = inputs.problem(day);
+    let input_path = inputs.real(day);
+    let recorded = fs::read_to_string(&problem_path)
+        .map(|markdown| recorded_answers(&markdown))
+        .unwrap_or_default();
+    let parsed = utils::read_input(&input_path)
+        .ok()
+        .map(|input| solution.parse(&input));
+    let params = solution.params(&[]).expect("the puzzle's own parameters are valid");
+
+    Part::BOTH
+        .into_iter()
+        .filter(|&part| solution.has_part(part))
+        .map(|part| {
+            let expected = recorded.get(part).cloned();
+            let start = Instant::now();
+            let (actual, memory) = match (&expected, &parsed) {
+                (Some(_), Some(Ok(parsed))) => {
+                    let (result, memory) =
+                        memory::measure(|| {
+                        solution.solve(part, parsed.as_ref(), params.as_ref(), &solution.budget(params.as_ref()))
+                    });
+                    (Some(result.map_err(Error::from)), memory)
+                }
+                (Some(_), Some(Err(e))) => (Some(Err(Error::from(e.clone()))), None),
+                _ => (None, None),
+            };
+            let duration = if actual.is_some() { start.elapsed() } else { Duration::ZERO };
+            let status = match (&expected, &actual) {
+                (_, Some(Err(e))) if e.is_timeout() => Status::TimedOut,
+                (_, Some(Err(_))) => Status::Error,
+                (Some(expected), Some(Ok(actual))) if expected == actual => Status::Pass,
+                (Some(_), Some(Ok(_))) => Status::Fail,
+                _ => Status::Missing,
+            };
+
+            Verification {
+                day,
+                part,
+                expected,
+                actual,
+                status,
+                duration,
+                memory,
+                problem_path: problem_path.clone(),
+                input_path: input_path.clone(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_the_answers_at_the_part_two_heading() {
+        let markdown = "\
+## Part One
+Example answer **7**.
+
+**Your puzzle answer was 964.**
+
+## Part Two
+**Your puzzle answer was 25663320831.**
+";
+        let answers = recorded_answers(markdown);
+        assert_eq!(answers.part1, Some(Answer::from(964i64)));
+        assert_eq!(answers.part2, Some(Answer::from(25663320831i64)));
+        assert_eq!(answers.get(Part::Two), answers.part2.as_ref());
+    }
+
+    #[test]
+    fn leaves_part2_empty_until_it_is_solved() {
+        let answers = recorded_answers("**Your puzzle answer was 5872.**\n\n## Part Two\nNot solved yet.\n");
+        assert_eq!(answers, RecordedAnswers { part1: Some(Answer::from(5872i64)), part2: None });
+
+        let answers = recorded_answers("**Your puzzle answer was 5872.**\n");
+        assert_eq!(answers, RecordedAnswers { part1: Some(Answer::from(5872i64)), part2: None });
+    }
+
+    #[test]
+    fn reads_non_numeric_and_wide_answers() {
+        assert_eq!(find_answer("Your puzzle answer was ABC-DEF.\n"), Some(Answer::from("ABC-DEF")));
+        assert_eq!(find_answer("**Your puzzle answer was  0,1,2**"), Some(Answer::from("0,1,2")));
+        assert_eq!(
+            find_answer("Your puzzle answer was 170141183460469231731687303715884105727"),
+            Some(Answer::from(i128::MAX))
+        );
+    }
+
+    #[test]
+    fn finds_no_answer_without_one_recorded() {
+        assert_eq!(recorded_answers(""), RecordedAnswers::default());
+        assert_eq!(recorded_answers("## Part Two\nYour puzzle answer was .\n"), RecordedAnswers::default());
+        assert_eq!(find_answer("Your puzzle answer was **"), None);
+        assert_eq!(find_answer("The answer was 12."), None);
+    }
+}
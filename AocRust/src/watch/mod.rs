@@ -0,0 +1,245 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::budget;
+use crate::inputs::Inputs;
+use crate::memory;
+use crate::parallel;
+use crate::report::{self, Outcome, Record};
+use crate::runner;
+use crate::solution::DynSolution;
+use crate::utils::format_cost;
+
+/// How often the files are checked when no interval is given
+pub const DEFAULT_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Build directory for the rebuilt copies, apart from the running binary so
+/// that rebuilding never has to overwrite an executable that is in use
+const REBUILD_TARGET_DIR: &str = "target/watch";
+
+/// A day's source file in this crate
+pub fn source_path(day: u32) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("src")
+        .join(format!("day{:02}", day))
+        .join("mod.rs")
+}
+
+/// Modification time and length of a file, `None` while it does not exist
+type Stamp = Option<(SystemTime, u64)>;
+
+fn stamp(path: &Path) -> Stamp {
+    let metadata = fs::metadata(path).ok()?;
+    Some((metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH), metadata.len()))
+}
+
+/// Stamps of the day's source, inputs and manifest; the real input is watched
+/// even before it exists so downloading it triggers a run
+fn snapshot(day: u32, inputs: &Inputs) -> Vec<(PathBuf, Stamp)> {
+    let mut paths = inputs.day_files(day);
+    if !paths.contains(&inputs.real(day)) {
+        paths.push(inputs.real(day));
+    }
+    paths.push(inputs.cases(day));
+    paths.push(source_path(day));
+    paths
+        .into_iter()
+        .map(|path| {
+            let stamp = stamp(&path);
+            (path, stamp)
+        })
+        .collect()
+}
+
+/// Files that were added, removed or modified between two snapshots
+fn changed_files(before: &[(PathBuf, Stamp)], after: &[(PathBuf, Stamp)]) -> Vec<PathBuf> {
+    let stamp_in = |snapshot: &[(PathBuf, Stamp)], path: &Path| {
+        snapshot.iter().find(|(other, _)| other == path).and_then(|(_, stamp)| *stamp)
+    };
+    let mut changed: Vec<PathBuf> = before
+        .iter()
+        .chain(after)
+        .map(|(path, _)| path.clone())
+        .filter(|path| stamp_in(before, path) != stamp_in(after, path))
+        .collect();
+    changed.sort();
+    changed.dedup();
+    changed
+}
+
+/// Runs a day's examples and real input, then again whenever one of its input
+/// files or its source file changes, printing which answers changed since the
+/// previous run. Input changes rerun the day in this process; once the source
+/// changes, a fresh build of the crate runs it instead. Only returns when the
+/// process is interrupted.
+pub fn watch(solution: &dyn DynSolution, inputs: &Inputs, interval: Duration) {
+    let day = solution.day();
+    println!(
+        "Watching {} and {} for changes (Ctrl-C to stop)",
+        source_path(day).display(),
+        inputs.root().join(format!("day{:02}*.txt", day)).display()
+    );
+    println!("\n--- Day {}: {} ---", day, solution.title());
+
+    let mut files = snapshot(day, inputs);
+    let mut previous = runner::run_day(solution, inputs, true);
+    // Once the source has changed, this binary's copy of the day is out of date
+    let mut rebuild = false;
+
+    loop {
+        thread::sleep(interval);
+        let mut current = snapshot(day, inputs);
+        let changed = changed_files(&files, &current);
+        if changed.is_empty() {
+            continue;
+        }
+
+        // Editors often save in several writes, so wait until the files settle
+        loop {
+            thread::sleep(interval);
+            let settled = snapshot(day, inputs);
+            if settled == current {
+                break;
+            }
+            current = settled;
+        }
+        files = current;
+
+        let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let names: Vec<String> = changed
+            .iter()
+            .map(|path| match path.strip_prefix(manifest_dir) {
+                Ok(source) => source.display().to_string(),
+                Err(_) => file_name(path),
+            })
+            .collect();
+        println!("\n--- Day {}: {} changed ---", day, names.join(", "));
+        rebuild |= changed.contains(&source_path(day));
+
+        let records = if rebuild {
+            run_rebuilt(day, inputs)
+        } else {
+            Ok(runner::run_day(solution, inputs, true))
+        };
+        match records {
+            Ok(records) => {
+                print_changes(&previous, &records);
+                previous = records;
+            }
+            Err(e) => eprintln!("{}", e),
+        }
+    }
+}
+
+/// Builds the crate with the day's new source and runs its tests in a child
+/// process, printing one line per record
+fn run_rebuilt(day: u32, inputs: &Inputs) -> Result<Vec<Record>, String> {
+    println!("Rebuilding to pick up the new source...");
+
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let inputs_root = fs::canonicalize(inputs.root()).unwrap_or_else(|_| inputs.root().to_path_buf());
+    let mut command = Command::new(env::var_os("CARGO").unwrap_or_else(|| "cargo".into()));
+    command
+        .current_dir(manifest_dir)
+        .args(["run", "--quiet", "--target-dir"])
+        .arg(manifest_dir.join(REBUILD_TARGET_DIR));
+    if !cfg!(debug_assertions) {
+        command.arg("--release");
+    }
+    if memory::enabled() {
+        command.args(["--features", "alloc-stats"]);
+    }
+    command
+        .args(["--", "test", "--day", &day.to_string(), "--format", "csv", "--inputs"])
+        .arg(inputs_root)
+        .args(["--jobs", &parallel::jobs().to_string()]);
+    let limits = budget::limits();
+    if let Some(time) = limits.time {
+        command.args(["--timeout", &format!("{}s", time.as_secs_f64())]);
+    }
+    if let Some(steps) = limits.steps {
+        command.args(["--max-steps", &steps.to_string()]);
+    }
+
+    // Build errors go straight to the terminal through the inherited stderr
+    let output = command
+        .output()
+        .map_err(|e| format!("cannot run cargo to rebuild day {}: {}", day, e))?;
+    let records = report::from_csv(&String::from_utf8_lossy(&output.stdout))
+        .map_err(|_| format!("rebuilding day {} failed; fix the error and save again", day))?;
+
+    for record in &records {
+        println!(
+            "{} ({} {}): {} [{}]",
+            record.part,
+            record.input,
+            file_name(Path::new(&record.path)),
+            describe(&record.outcome),
+            format_cost(&record.outcome)
+        );
+    }
+    Ok(records)
+}
+
+/// Prints every part and input whose answer or status differs from the previous run.
+/// Records are matched by file name, since a rebuilt run may see the inputs
+/// directory through a different path.
+fn print_changes(previous: &[Record], current: &[Record]) {
+    let key = |record: &Record| (record.part, record.input, file_name(Path::new(&record.path)));
+    let describe_in = |records: &[Record], wanted: &(_, _, String)| {
+        records
+            .iter()
+            .find(|record| key(record) == *wanted)
+            .map(|record| describe(&record.outcome))
+    };
+
+    // Current records first, then those that are gone, e.g. a deleted example
+    let mut keys = Vec::new();
+    for record in current.iter().chain(previous) {
+        let record_key = key(record);
+        if !keys.contains(&record_key) {
+            keys.push(record_key);
+        }
+    }
+
+    let mut changes = Vec::new();
+    for wanted in &keys {
+        let (before, after) = (describe_in(previous, wanted), describe_in(current, wanted));
+        if before != after {
+            let (part, input, name) = wanted;
+            changes.push(format!(
+                "  {} ({} {}): {} → {}",
+                part,
+                input,
+                name,
+                before.unwrap_or_else(|| "not run".to_string()),
+                after.unwrap_or_else(|| "not run".to_string())
+            ));
+        }
+    }
+
+    if changes.is_empty() {
+        println!("\nNo answers changed since the last run");
+    } else {
+        println!("\nChanged since the last run:\n{}", changes.join("\n"));
+    }
+}
+
+/// An outcome's answer and status, or its status and error
+fn describe(outcome: &Outcome) -> String {
+    match (&outcome.answer, &outcome.error) {
+        (Some(answer), _) => format!("{} ({})", answer, outcome.status),
+        (None, Some(error)) => format!("{} ({})", outcome.status, error),
+        (None, None) => outcome.status.to_string(),
+    }
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string())
+}
@@ -0,0 +1,79 @@
+//! Every day's example cases and recorded real answers as `cargo test` tests.
+//!
+//! The examples are checked by [`runner::run_examples`] and the real inputs by
+//! [`verify::verify_day`], the same code behind `aoc_rust test` and
+//! `aoc_rust verify`, so `cargo test` and the runner cannot disagree. Real
+//! inputs or recorded answers that are not there are skipped.
+
+use std::time::Duration;
+
+use aoc_rust::budget::{self, Limits};
+use aoc_rust::inputs::Inputs;
+use aoc_rust::registry;
+use aoc_rust::runner;
+use aoc_rust::verify::{self, Status};
+
+/// Long enough for any day in a test build, so a search that never ends fails
+/// the test instead of hanging the run
+const TIME_LIMIT: Duration = Duration::from_secs(120);
+
+fn check_day(day: u32) {
+    budget::set_limits(Limits { time: Some(TIME_LIMIT), steps: None });
+    let inputs = Inputs::locate(None).unwrap_or_else(|e| panic!("{}", e));
+    let solution = registry::get(day).unwrap_or_else(|| panic!("day {} is not registered", day));
+    let mut failures = Vec::new();
+
+    for record in runner::run_examples(solution, &inputs, false) {
+        if record.outcome.status.is_failure() {
+            let outcome = &record.outcome;
+            let got = match (&outcome.answer, &outcome.error) {
+                (Some(answer), _) => answer.to_string(),
+                (None, error) => format!("error: {}", error.as_deref().unwrap_or_default()),
+            };
+            let expected = outcome.expected.as_ref().map_or("-".to_string(), ToString::to_string);
+            failures.push(format!("{} on {}: expected {}, got {}", record.part, record.path, expected, got));
+        }
+    }
+    for verification in verify::verify_day(solution, &inputs) {
+        if !matches!(verification.status, Status::Pass | Status::Missing) {
+            failures.push(verification.to_string());
+        }
+    }
+
+    assert!(failures.is_empty(), "day {} failed:\n{}", day, failures.join("\n"));
+}
+
+/// A test per day, plus the list of days they cover
+macro_rules! day_tests {
+    ($($name:ident => $day:expr,)*) => {
+        const TESTED_DAYS: &[u32] = &[$($day),*];
+
+        $(
+            #[test]
+            fn $name() {
+                check_day($day);
+            }
+        )*
+    };
+}
+
+day_tests! {
+    day01 => 1,
+    day02 => 2,
+    day03 => 3,
+    day04 => 4,
+    day05 => 5,
+    day06 => 6,
+    day07 => 7,
+    day08 => 8,
+    day09 => 9,
+    day10 => 10,
+    day11 => 11,
+    day12 => 12,
+}
+
+#[test]
+fn every_registered_day_is_tested() {
+    let registered: Vec<u32> = registry::all().iter().map(|solution| solution.day()).collect();
+    assert_eq!(registered, TESTED_DAYS, "add new days to day_tests! in tests/puzzles.rs");
+}